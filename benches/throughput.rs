@@ -0,0 +1,128 @@
+use std::fmt;
+use std::io;
+use std::io::BufRead;
+
+use ansi_term::Colour::Fixed;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use itertools::Itertools;
+
+use coloursum::{coloursum, FormatOptions, FormattableLine, FormatterRegistry, Line};
+
+/// Number of lines in the generated manifest.
+const LINES: usize = 10_000;
+
+/// Generates a `sha256sum` style manifest, with a different digest on every line.
+fn manifest() -> Vec<u8> {
+    let mut manifest = String::new();
+    let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+
+    for index in 0..LINES {
+        for _ in 0..4 {
+            // xorshift, for cheap, repeatable, well-distributed digests
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            manifest.push_str(&format!("{:016x}", state));
+        }
+        manifest.push_str(&format!("  ./dataset/part-{:08}.parquet\n", index));
+    }
+
+    manifest.into_bytes()
+}
+
+/// `ANSIColouredLine`, as it was before formatters could write into a
+/// `fmt::Write`; allocating a `String` for each byte, and for the hash.
+struct AllocatingANSIColouredLine(FormattableLine);
+
+impl From<Vec<u8>> for AllocatingANSIColouredLine {
+    fn from(contents: Vec<u8>) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl fmt::Display for AllocatingANSIColouredLine {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.to_formatted(formatter)
+    }
+}
+
+impl Line for AllocatingANSIColouredLine {
+    fn get_line(&self) -> &FormattableLine {
+        &self.0
+    }
+
+    fn format_hash(hash: String) -> String {
+        use std::num::ParseIntError;
+
+        let result: Result<String, ParseIntError> = hash
+            .chars()
+            .chunks(2)
+            .into_iter()
+            .map(|byte| {
+                let ord_string = String::from_iter(byte);
+                u8::from_str_radix(&ord_string, 16)
+                    .map(|ordinal| Fixed(ordinal).paint(ord_string).to_string())
+            })
+            .collect();
+
+        result.unwrap_or(hash)
+    }
+}
+
+/// Formats each line the way `Line::coloursum` did before streaming
+/// formatting, allocating a `String` for each line.
+fn allocating_coloursum<L: Line>(input: &[u8], mut output: impl io::Write) -> io::Result<()> {
+    for line in input.lines() {
+        writeln!(output, "{}", L::from(line?.into_bytes()))?;
+    }
+
+    Ok(())
+}
+
+fn throughput(criterion: &mut Criterion) {
+    let input = manifest();
+    let registry = FormatterRegistry::default();
+    // written to, rather than `io::sink()`, so that the cost of actually
+    // producing the output is measured; reused so that growing it isn't
+    let mut output = Vec::with_capacity(input.len() * 16);
+
+    let mut group = criterion.benchmark_group("throughput");
+    group.throughput(Throughput::Bytes(input.len() as u64));
+
+    group.bench_with_input(
+        BenchmarkId::new("allocating", "ansi-colours"),
+        &input,
+        |bencher, input| {
+            bencher.iter(|| {
+                output.clear();
+                allocating_coloursum::<AllocatingANSIColouredLine>(input, &mut output).unwrap()
+            })
+        },
+    );
+
+    for name in registry.names() {
+        let formatter = registry.get(name).unwrap();
+
+        group.bench_with_input(
+            BenchmarkId::new("streaming", name),
+            &input,
+            |bencher, input| {
+                bencher.iter(|| {
+                    output.clear();
+                    coloursum(
+                        formatter,
+                        &input[..],
+                        &mut output,
+                        &FormatOptions::default(),
+                    )
+                    .unwrap()
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, throughput);
+criterion_main!(benches);
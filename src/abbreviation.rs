@@ -0,0 +1,374 @@
+use std::fmt;
+use std::iter;
+use std::str::FromStr;
+
+use crate::colour::{write_painted, Oklch, TermColour};
+use crate::colour_support::ColourSupport;
+use crate::escape_sequences::{visible_length, CharacterKind, EscapeTracker};
+use crate::format_options::FormatOptions;
+
+/// What stands in for the characters left out of an abbreviated digest.
+const ELLIPSIS: &str = "…";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// How to abbreviate formatted digests, showing only their first
+/// and last few characters, such as `3e08ba70…684bda`.
+///
+/// The characters left out are replaced with an ellipsis, coloured
+/// according to the whole digest, so digests which differ only in the
+/// characters left out still look different, as long as colours are enabled.
+///
+/// There are only so many colours to choose from, though, so some pairs of
+/// digests do share an ellipsis colour: hardly any with 24-bit colour, but
+/// around 1 in 200 with the xterm 256 colour palette, and 1 in 14 with only
+/// the basic ANSI colours. A `Palette` other than the standard one has
+/// fewer colours still, so shares them more often.
+///
+/// Formatters which spell digests out in words or syllables count those,
+/// rather than characters, so that no word is cut in half.
+///
+/// ```rust
+/// use coloursum::{Abbreviation, ColourSupport, FormatOptions, Line, PlainLine};
+///
+/// let line = PlainLine::from("b7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs".to_string());
+/// let mut output = String::new();
+///
+/// line.to_formatted_with_options(
+///     &mut output,
+///     &FormatOptions {
+///         abbreviation: Some(Abbreviation::new(8, 6)),
+///         colour_support: ColourSupport::None,
+///         ..FormatOptions::default()
+///     },
+/// )
+/// .unwrap();
+///
+/// assert_eq!(output, "b7527e0e…d5d225  ./src/main.rs");
+/// ```
+pub struct Abbreviation {
+    /// How many characters to show from the start of the digest.
+    pub head: usize,
+    /// How many characters to show from the end of the digest.
+    pub tail: usize,
+}
+
+impl Abbreviation {
+    /// Creates an `Abbreviation` which shows the first `head`
+    /// and last `tail` characters of each digest.
+    pub fn new(head: usize, tail: usize) -> Self {
+        Self { head, tail }
+    }
+
+    /// How many characters or units an abbreviated digest takes up,
+    /// counting the ellipsis as one; digests no longer than this are
+    /// left as they are.
+    ///
+    /// Saturates rather than overflowing, as any lengths may be given.
+    pub(crate) fn shown_length(self) -> usize {
+        self.head.saturating_add(self.tail).saturating_add(1)
+    }
+}
+
+impl Default for Abbreviation {
+    /// Shows the first 8 and last 6 characters of each digest.
+    fn default() -> Self {
+        Self::new(8, 6)
+    }
+}
+
+impl FromStr for Abbreviation {
+    type Err = String;
+
+    /// Parses an abbreviation from either a single number, which is used for
+    /// both the head and tail, or a pair of numbers separated by a comma.
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let parse = |number: &str| {
+            number
+                .trim()
+                .parse::<usize>()
+                .map_err(|error| format!("`{}` isn't a valid length: {}", number, error))
+        };
+
+        match string.split_once(',') {
+            Some((head, tail)) => Ok(Self::new(parse(head)?, parse(tail)?)),
+            None => parse(string).map(|length| Self::new(length, length)),
+        }
+    }
+}
+
+/// Writes `formatted`, a formatted version of `digest`, to `to`,
+/// abbreviated as configured by `abbreviation`.
+///
+/// `write_part` is used to write each of the parts which are kept,
+/// so that they can be grouped separately.
+pub(crate) fn write_abbreviated(
+    digest: &str,
+    formatted: &str,
+    abbreviation: Abbreviation,
+    to: &mut dyn fmt::Write,
+    options: &FormatOptions,
+    mut write_part: impl FnMut(&str, &mut dyn fmt::Write) -> fmt::Result,
+) -> fmt::Result {
+    let length = visible_length(formatted);
+
+    // only abbreviate if it makes the digest shorter
+    if length <= abbreviation.shown_length() {
+        return write_part(formatted, to);
+    }
+
+    let (head_end, tail_start) =
+        match find_cuts(formatted, abbreviation.head, length - abbreviation.tail) {
+            Some(cuts) => cuts,
+            // styles span the whole of the middle, so there's nothing to leave out
+            None => return write_part(formatted, to),
+        };
+
+    write_part(&formatted[..head_end], to)?;
+    write_ellipsis(digest, to, options)?;
+    write_part(&formatted[tail_start..], to)
+}
+
+/// Writes the ellipsis which stands in for what's left out
+/// of `digest`, coloured according to the whole digest.
+pub(crate) fn write_ellipsis(
+    digest: &str,
+    to: &mut dyn fmt::Write,
+    options: &FormatOptions,
+) -> fmt::Result {
+    write_painted(
+        to,
+        ellipsis_colour(digest, options),
+        ELLIPSIS,
+        options.colour_support,
+    )
+}
+
+/// Finds where to cut `formatted` so that the first part has `head`
+/// visible characters, and the second starts at the visible character
+/// numbered `tail_start`, without splitting any styles across the cut.
+///
+/// Returns `None` if there's nothing between the two parts.
+fn find_cuts(formatted: &str, head: usize, tail_start: usize) -> Option<(usize, usize)> {
+    let mut escapes = EscapeTracker::new();
+    let mut visible = 0;
+    let mut head_end = None;
+    // the last place a cut could be made, before any styles are applied
+    let mut last_boundary = 0;
+    // the end is treated as one more visible character, so that the tail can be empty
+    let characters = formatted
+        .char_indices()
+        .map(|(offset, character)| (offset, Some(character)))
+        .chain(iter::once((formatted.len(), None)));
+
+    for (offset, character) in characters {
+        let styled = escapes.is_styled();
+        let kind = character.map_or(CharacterKind::Visible, |character| {
+            escapes.advance(character)
+        });
+
+        if !styled && kind != CharacterKind::Escaped {
+            if visible >= head && head_end.is_none() {
+                head_end = Some(offset);
+            }
+            last_boundary = offset;
+        }
+
+        if kind == CharacterKind::Visible {
+            if visible == tail_start {
+                return head_end
+                    .filter(|&head_end| head_end < last_boundary)
+                    .map(|head_end| (head_end, last_boundary));
+            }
+            visible += 1;
+        }
+    }
+
+    None
+}
+
+/// Picks a colour for the ellipsis, using every byte of `digest`.
+///
+/// With the standard palette, the colour is picked from a hash of `digest`
+/// among every colour `options.colour_support` allows, apart from black
+/// and white, so that digests which differ only in the middle rarely share
+/// an ellipsis colour. Other palettes only have a colour for each byte,
+/// so the hash is folded down to one byte.
+fn ellipsis_colour(digest: &str, options: &FormatOptions) -> TermColour {
+    // 32-bit FNV-1a
+    let hash = digest.bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    });
+    let folded = hash
+        .to_le_bytes()
+        .iter()
+        .fold(0, |folded, byte| folded ^ byte);
+
+    if let Some(colour) = options.palette.colour(folded) {
+        let (red, green, blue) = colour.to_rgb();
+        return TermColour::Rgb(red, green, blue);
+    }
+
+    match options.colour_support {
+        // the basic colours from red to bright cyan
+        ColourSupport::None | ColourSupport::Basic => TermColour::Fixed(1 + (hash % 14) as u8),
+        // the colour cube, from its first blue to its last yellow
+        ColourSupport::Extended => TermColour::Fixed(17 + (hash % 214) as u8),
+        ColourSupport::Truecolour => {
+            // 16 bits pick the hue, and 8 more the lightness, in the same
+            // range as `TruecolourLine` uses
+            let (red, green, blue) = Oklch {
+                lightness: 0.66 + 0.2 * f32::from((hash >> 16) as u8) / 255.0,
+                chroma: 0.14,
+                hue: f32::from(hash as u16) / 65536.0 * 360.0,
+            }
+            .to_rgb();
+
+            TermColour::Rgb(red, green, blue)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn from_str_works() {
+        use super::Abbreviation;
+
+        assert_eq!("8,6".parse(), Ok(Abbreviation::new(8, 6)));
+        assert_eq!("4".parse(), Ok(Abbreviation::new(4, 4)));
+        assert!("eight".parse::<Abbreviation>().is_err());
+        assert!("8,".parse::<Abbreviation>().is_err());
+    }
+
+    #[test]
+    fn find_cuts_works() {
+        use super::find_cuts;
+
+        assert_eq!(find_cuts("b7527e0e28c09f6f", 4, 12), Some((4, 12)));
+        assert_eq!(
+            find_cuts("\x1b[1mb7\x1b[0m\x1b[2m52\x1b[0m\x1b[3m7e\x1b[0m", 2, 4),
+            Some((10, 20))
+        );
+        // styles aren't split, even if that means showing more characters
+        assert_eq!(
+            find_cuts("\x1b[1mb7\x1b[0m\x1b[2m527e\x1b[0m0e28", 3, 7),
+            Some((22, 23))
+        );
+        assert_eq!(find_cuts("\x1b[1mb752\x1b[0m7e", 1, 3), None);
+        // a cut at the very end leaves an empty tail
+        assert_eq!(find_cuts("b7527e0e", 4, 8), Some((4, 8)));
+        assert_eq!(
+            find_cuts("\x1b[1mb7\x1b[0m\x1b[2m52\x1b[0m", 2, 4),
+            Some((10, 20))
+        );
+    }
+
+    #[test]
+    fn write_abbreviated_works() {
+        use super::{write_abbreviated, Abbreviation};
+        use crate::{ColourSupport, FormatOptions};
+
+        let abbreviate_with = |abbreviation, digest: &str, formatted: &str, colour_support| {
+            let mut output = String::new();
+            write_abbreviated(
+                digest,
+                formatted,
+                abbreviation,
+                &mut output,
+                &FormatOptions {
+                    colour_support,
+                    ..FormatOptions::default()
+                },
+                |part, to| to.write_str(part),
+            )
+            .unwrap();
+            output
+        };
+        let abbreviate = |digest: &str, formatted: &str, colour_support| {
+            abbreviate_with(Abbreviation::new(4, 2), digest, formatted, colour_support)
+        };
+
+        assert_eq!(
+            abbreviate("b7527e0e28", "b7527e0e28", ColourSupport::None),
+            "b752…28"
+        );
+        // nothing is left out of short digests
+        assert_eq!(
+            abbreviate("b7527e0", "b7527e0", ColourSupport::None),
+            "b7527e0"
+        );
+        assert_eq!(
+            abbreviate_with(
+                Abbreviation::new(usize::MAX, usize::MAX),
+                "b7527e0e28",
+                "b7527e0e28",
+                ColourSupport::None
+            ),
+            "b7527e0e28"
+        );
+        assert_eq!(
+            abbreviate(
+                "b752",
+                "\x1b[1mb7\x1b[0m\x1b[2m52\x1b[0m\x1b[3m7e\x1b[0m\x1b[4m0e\x1b[0m",
+                ColourSupport::None
+            ),
+            "\x1b[1mb7\x1b[0m\x1b[2m52\x1b[0m…\x1b[4m0e\x1b[0m"
+        );
+
+        // the head or tail can be left out entirely
+        assert_eq!(
+            abbreviate_with(
+                Abbreviation::new(4, 0),
+                "b7527e0e28",
+                "b7527e0e28",
+                ColourSupport::None
+            ),
+            "b752…"
+        );
+        assert_eq!(
+            abbreviate_with(
+                Abbreviation::new(0, 0),
+                "b7527e0e28",
+                "b7527e0e28",
+                ColourSupport::None
+            ),
+            "…"
+        );
+        assert_eq!(
+            abbreviate_with(
+                Abbreviation::new(0, 2),
+                "b7527e0e28",
+                "b7527e0e28",
+                ColourSupport::None
+            ),
+            "…28"
+        );
+
+        // digests which differ only in the middle have different ellipses
+        assert_ne!(
+            abbreviate("b7527e0e28", "b7527e0e28", ColourSupport::Truecolour),
+            abbreviate("b7520000e28", "b7527e0e28", ColourSupport::Truecolour),
+        );
+    }
+
+    #[test]
+    fn ellipsis_colour_uses_every_colour_allowed() {
+        use super::ellipsis_colour;
+        use crate::{ColourSupport, FormatOptions};
+        use std::collections::HashSet;
+
+        for (colour_support, count) in [(ColourSupport::Extended, 214), (ColourSupport::Basic, 14)]
+        {
+            let options = FormatOptions {
+                colour_support,
+                ..FormatOptions::default()
+            };
+            let colours: HashSet<_> = (0..10_000)
+                .map(|digest| format!("{:032x}", digest))
+                .map(|digest| ellipsis_colour(&digest, &options))
+                .collect();
+
+            assert_eq!(colours.len(), count, "{:?}", colour_support);
+        }
+    }
+}
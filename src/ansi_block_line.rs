@@ -0,0 +1,121 @@
+use std::convert::From;
+use std::fmt;
+use std::fmt::Display;
+
+use crate::ansi_coloured_line::byte_colour;
+use crate::base_line::{format_to_string, FormattableLine, Line};
+use crate::colour::{theme_independent_colour, write_block, TermColour};
+use crate::format_options::FormatOptions;
+
+#[derive(Debug)]
+/// Line with ANSI formatting which paints each byte as a block of
+/// background colour, with black or white text, whichever is more legible.
+pub struct ANSIBlockLine(FormattableLine);
+impl From<String> for ANSIBlockLine {
+    fn from(contents: String) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl From<Vec<u8>> for ANSIBlockLine {
+    fn from(contents: Vec<u8>) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl Display for ANSIBlockLine {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.to_formatted(formatter)
+    }
+}
+
+impl Line for ANSIBlockLine {
+    fn get_line(&self) -> &FormattableLine {
+        &self.0
+    }
+
+    fn format_hash(hash: String) -> String {
+        format_to_string(&hash, Self::write_hash)
+    }
+
+    /// Formats a base16-format hash or digest.
+    ///
+    /// Each 8-bit hexadecimal digit will be given a background of the
+    /// corresponding xterm colour, as `ANSIColouredLine` would colour it;
+    /// apart from the 16 basic colours, which vary between terminal themes,
+    /// and are replaced by the closest colour whose appearance is known.
+    fn write_hash(hash: &str, to: &mut dyn fmt::Write) -> fmt::Result {
+        Self::write_hash_with_options(hash, to, &FormatOptions::default())
+    }
+
+    /// Formats a base16-format hash or digest.
+    ///
+    /// Each 8-bit hexadecimal digit will be given a background of the
+    /// colour `ANSIColouredLine` would colour it with `options`.
+    fn write_hash_with_options(
+        hash: &str,
+        to: &mut dyn fmt::Write,
+        options: &FormatOptions,
+    ) -> fmt::Result {
+        // if there are any non-base16 characters, write the original value
+        if !hash.bytes().all(|character| character.is_ascii_hexdigit()) {
+            return to.write_str(hash);
+        }
+
+        // map over every two characters
+        for byte in hash.as_bytes().chunks(2) {
+            // these are all ASCII characters, so this can't fail
+            let ord_string = std::str::from_utf8(byte).unwrap_or_default();
+
+            // parse those two characters as a u8, and colour them in,
+            // avoiding the basic colours, so the text's always legible
+            match u8::from_str_radix(ord_string, 16) {
+                Ok(ordinal) => write_block(
+                    to,
+                    TermColour::Fixed(theme_independent_colour(byte_colour(
+                        ordinal,
+                        options.palette,
+                    ))),
+                    ord_string,
+                    options.colour_support,
+                )?,
+                Err(_) => to.write_str(ord_string)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn display_works() {
+        use super::ANSIBlockLine;
+
+        assert_eq!(
+            format!("{}", ANSIBlockLine::from("MD5 (./src/main.rs) = b7527e0e28c09f6f62dd2d4197d5d225".to_string())),
+            "MD5 (./src/main.rs) = \u{1b}[48;5;183;38;5;16mb7\u{1b}[0m\u{1b}[48;5;82;38;5;16m52\u{1b}[0m\u{1b}[48;5;126;38;5;231m7e\u{1b}[0m\u{1b}[48;5;51;38;5;16m0e\u{1b}[0m\u{1b}[48;5;40;38;5;16m28\u{1b}[0m\u{1b}[48;5;192;38;5;16mc0\u{1b}[0m\u{1b}[48;5;159;38;5;16m9f\u{1b}[0m\u{1b}[48;5;111;38;5;16m6f\u{1b}[0m\u{1b}[48;5;98;38;5;16m62\u{1b}[0m\u{1b}[48;5;221;38;5;16mdd\u{1b}[0m\u{1b}[48;5;45;38;5;16m2d\u{1b}[0m\u{1b}[48;5;65;38;5;16m41\u{1b}[0m\u{1b}[48;5;151;38;5;16m97\u{1b}[0m\u{1b}[48;5;213;38;5;16md5\u{1b}[0m\u{1b}[48;5;210;38;5;16md2\u{1b}[0m\u{1b}[48;5;37;38;5;16m25\u{1b}[0m"
+        )
+    }
+
+    #[test]
+    fn format_hash_works() {
+        use super::ANSIBlockLine;
+        use crate::Line;
+
+        assert_eq!(
+            ANSIBlockLine::format_hash("00e2ff".to_string()),
+            "\u{1b}[48;5;16;38;5;231m00\u{1b}[0m\u{1b}[48;5;226;38;5;16me2\u{1b}[0m\u{1b}[48;5;255;38;5;16mff\u{1b}[0m"
+        );
+    }
+
+    #[test]
+    fn format_hash_doesnt_crash_on_non_base16_characters() {
+        use super::ANSIBlockLine;
+        use crate::Line;
+
+        assert_eq!(ANSIBlockLine::format_hash("ASDF".to_string()), "ASDF");
+        assert_eq!(ANSIBlockLine::format_hash("😄".to_string()), "😄");
+    }
+}
@@ -1,12 +1,11 @@
 use std::convert::From;
 use std::fmt;
 use std::fmt::Display;
-use std::iter::FromIterator;
 
-use ansi_term::Colour::Fixed;
-use itertools::Itertools;
-
-use crate::base_line::{FormattableLine, Line};
+use crate::base_line::{format_to_string, FormattableLine, Line};
+use crate::colour::{write_painted, xterm_colour, TermColour};
+use crate::format_options::FormatOptions;
+use crate::palette::Palette;
 
 #[derive(Debug)]
 /// Line with naïve ANSI Colour code formatting.
@@ -17,6 +16,12 @@ impl From<String> for ANSIColouredLine {
     }
 }
 
+impl From<Vec<u8>> for ANSIColouredLine {
+    fn from(contents: Vec<u8>) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
 impl Display for ANSIColouredLine {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         self.to_formatted(formatter)
@@ -28,29 +33,64 @@ impl Line for ANSIColouredLine {
         &self.0
     }
 
+    fn format_hash(hash: String) -> String {
+        format_to_string(&hash, Self::write_hash)
+    }
+
     /// Formats a base16-format hash or digest.
     ///
     /// Each 8-bit hexadecimal digit will be coloured
     /// with the corresponding xterm colour.
-    fn format_hash(hash: String) -> String {
-        use std::num::ParseIntError;
+    fn write_hash(hash: &str, to: &mut dyn fmt::Write) -> fmt::Result {
+        Self::write_hash_with_options(hash, to, &FormatOptions::default())
+    }
+
+    /// Formats a base16-format hash or digest.
+    ///
+    /// Each 8-bit hexadecimal digit will be coloured with the corresponding
+    /// xterm colour, or, if `options` selects a palette other than the
+    /// standard one, the closest xterm colour to the palette's colour.
+    /// Colours are downgraded to those `options.colour_support` allows.
+    fn write_hash_with_options(
+        hash: &str,
+        to: &mut dyn fmt::Write,
+        options: &FormatOptions,
+    ) -> fmt::Result {
+        // if there are any non-base16 characters, write the original value
+        if !hash.bytes().all(|character| character.is_ascii_hexdigit()) {
+            return to.write_str(hash);
+        }
 
         // map over every two characters
-        let result: Result<String, ParseIntError> = hash
-            .chars()
-            .chunks(2)
-            .into_iter()
-            .map(|byte| {
-                let ord_string = String::from_iter(byte);
-                // attempt to parse those two characters as a u8,
-                // and if that works, colour them in
-                u8::from_str_radix(&ord_string, 16)
-                    .map(|ordinal| Fixed(ordinal).paint(ord_string).to_string())
-            })
-            .collect();
-
-        // if there was an error at any point, return the original value
-        result.unwrap_or(hash)
+        for byte in hash.as_bytes().chunks(2) {
+            // these are all ASCII characters, so this can't fail
+            let ord_string = std::str::from_utf8(byte).unwrap_or_default();
+
+            // parse those two characters as a u8, and colour them in
+            match u8::from_str_radix(ord_string, 16) {
+                Ok(ordinal) => {
+                    write_painted(
+                        to,
+                        TermColour::Fixed(byte_colour(ordinal, options.palette)),
+                        ord_string,
+                        options.colour_support,
+                    )?;
+                }
+                Err(_) => to.write_str(ord_string)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Picks the xterm colour for a byte of a digest, from `palette`.
+///
+/// With the standard palette, each byte's value is its colour.
+pub(crate) fn byte_colour(byte: u8, palette: Palette) -> u8 {
+    match palette.colour(byte) {
+        Some(colour) => xterm_colour(colour.to_rgb()).0,
+        None => byte,
     }
 }
 
@@ -79,6 +119,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn write_hash_with_options_uses_palette() {
+        use super::ANSIColouredLine;
+        use crate::{FormatOptions, Line, Palette};
+
+        let format = |palette| {
+            let mut formatted = String::new();
+            ANSIColouredLine::write_hash_with_options(
+                "4a4b",
+                &mut formatted,
+                &FormatOptions {
+                    palette,
+                    ..FormatOptions::default()
+                },
+            )
+            .unwrap();
+            formatted
+        };
+
+        assert_eq!(
+            format(Palette::Standard),
+            ANSIColouredLine::format_hash("4a4b".to_string())
+        );
+        assert_eq!(
+            format(Palette::Deuteranopia),
+            "\u{1b}[38;5;103m4a\u{1b}[0m\u{1b}[38;5;252m4b\u{1b}[0m"
+        );
+        assert_eq!(
+            format(Palette::Greyscale),
+            "\u{1b}[38;5;245m4a\u{1b}[0m\u{1b}[38;5;250m4b\u{1b}[0m"
+        );
+    }
+
     #[test]
     fn format_hash_doesnt_crash_on_non_base16_characters() {
         use super::ANSIColouredLine;
@@ -87,4 +160,42 @@ mod tests {
         ANSIColouredLine::format_hash("ASDF".to_string());
         ANSIColouredLine::format_hash("😄".to_string());
     }
+
+    #[test]
+    fn coloursum_preserves_line_endings() {
+        use super::ANSIColouredLine;
+        use crate::Line;
+
+        let input: &[u8] = b"MD5 (./src/main.rs) = b7527e0e28c09f6f62dd2d4197d5d225\r\nb7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs\nb7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs";
+        let mut output: Vec<u8> = Vec::new();
+
+        ANSIColouredLine::coloursum(input, &mut output).unwrap();
+
+        let hash = ANSIColouredLine::format_hash("b7527e0e28c09f6f62dd2d4197d5d225".to_string());
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            format!(
+                "MD5 (./src/main.rs) = {0}\r\n{0}  ./src/main.rs\n{0}  ./src/main.rs",
+                hash
+            )
+        );
+    }
+
+    #[test]
+    fn display_colours_escaped_lines() {
+        use super::ANSIColouredLine;
+        use crate::Line;
+
+        assert_eq!(
+            format!(
+                "{}",
+                ANSIColouredLine::from("\\b7527e0e28c09f6f62dd2d4197d5d225  ./a\\nb".to_string())
+            ),
+            format!(
+                "\\{}  ./a\\nb",
+                ANSIColouredLine::format_hash("b7527e0e28c09f6f62dd2d4197d5d225".to_string())
+            )
+        )
+    }
 }
@@ -4,39 +4,126 @@ use std::fmt::Display;
 use std::io;
 use std::io::{BufRead, Write};
 
+use crate::checksum_line::{decode_digest, unescape, ChecksumLine, ChecksumSpans};
+use crate::digest_formatter::{write_digest, DigestFormatter, LineFormatter};
+use crate::format_options::{FormatOptions, RecordSeparator};
+
 #[derive(Debug)]
 /// Representation of the formattable contents of a line of console output.
+///
+/// Lines are stored as raw bytes, so that filenames which are not valid
+/// UTF-8 are passed through unchanged.
 pub struct FormattableLine {
-    contents: String,
-    formattable_start: Option<usize>,
-    formattable_end: Option<usize>,
+    contents: Vec<u8>,
+    spans: Option<ChecksumSpans>,
+}
+
+impl FormattableLine {
+    /// The line, parsed into its parts, if it was recognisable
+    /// as the output of a checksum utility.
+    pub fn checksum(&self) -> Option<ChecksumLine<'_>> {
+        self.spans
+            .clone()
+            .map(|spans| ChecksumLine::from_spans(&self.contents, spans))
+    }
+
+    /// Whether the line was produced in binary mode; that is, whether
+    /// the hash is followed by the `*` binary mode marker.
+    pub fn is_binary(&self) -> bool {
+        matches!(self.checksum(), Some(checksum) if checksum.is_binary())
+    }
+
+    /// Writes the line to `to`, with its hash formatted by `formatter`,
+    /// as configured by `options`.
+    ///
+    /// Unless `options.unescape_filenames` is set, any bytes outside
+    /// of the checksum are written exactly as they were read.
+    pub fn write_with(
+        &self,
+        formatter: &dyn DigestFormatter,
+        to: &mut dyn Write,
+        options: &FormatOptions,
+    ) -> io::Result<()> {
+        write_line(
+            &self.contents,
+            self.spans.as_ref(),
+            formatter,
+            to,
+            options,
+            &mut String::new(),
+        )
+    }
+}
+
+impl From<Vec<u8>> for FormattableLine {
+    /// Converts a byte vector to a `FormattableLine`.
+    fn from(contents: Vec<u8>) -> Self {
+        let spans = ChecksumSpans::find(&contents);
+
+        Self { contents, spans }
+    }
 }
 
 impl From<String> for FormattableLine {
     /// Converts a `String` to a `FormattableLine`.
     fn from(contents: String) -> Self {
-        let mut formattable_start: Option<usize> = None;
-        let mut formattable_end: Option<usize> = None;
-
-        if let Some(suffix_start) = find_bsd_tag_line(&contents) {
-            formattable_start = Some(suffix_start);
-        } else if let Some(prefix_end) = find_sum_prefixed_line(&contents) {
-            formattable_end = Some(prefix_end);
-        }
-
-        Self {
-            contents,
-            formattable_start,
-            formattable_end,
-        }
+        Self::from(contents.into_bytes())
     }
 }
 
-/// Used to present a formattable line, which can be derived from a `String`.
-pub trait Line: Display + From<String> {
+/// Used to present a formattable line, which can be derived from raw bytes.
+pub trait Line: Display + From<Vec<u8>> {
     /// Formats the given checksum string.
     fn format_hash(hash: String) -> String;
 
+    /// Writes the given checksum string, formatted, to `to`.
+    ///
+    /// By default, this writes the result of `format_hash`; implementations
+    /// may override it to avoid allocating a `String` for every hash.
+    fn write_hash(hash: &str, to: &mut dyn fmt::Write) -> fmt::Result {
+        to.write_str(&Self::format_hash(hash.to_string()))
+    }
+
+    /// Writes the given checksum string, formatted as configured
+    /// by `options`, to `to`.
+    ///
+    /// By default, `options` are ignored, and this calls `write_hash`.
+    fn write_hash_with_options(
+        hash: &str,
+        to: &mut dyn fmt::Write,
+        options: &FormatOptions,
+    ) -> fmt::Result {
+        let _ = options;
+        Self::write_hash(hash, to)
+    }
+
+    /// Whether `write_hash_with_options` spells hashes out in whole units,
+    /// such as words or syllables, and groups and abbreviates them by unit
+    /// itself, as configured by `options.grouping` and `options.abbreviation`.
+    ///
+    /// By default, this is `false`, and what's written is grouped and
+    /// abbreviated by visible characters instead.
+    fn writes_units() -> bool {
+        false
+    }
+
+    /// Writes any extra lines to display below the line containing
+    /// `checksum`, as configured by `options`, to `to`.
+    ///
+    /// Lines are separated by `\n`, with no newline after the last,
+    /// even if `options.record_separator` is `RecordSeparator::Nul`,
+    /// so that they still display as separate lines.
+    /// By default, nothing is written, so each line of input
+    /// produces one line of output.
+    fn write_trailer(
+        checksum: &ChecksumLine,
+        to: &mut dyn fmt::Write,
+        options: &FormatOptions,
+    ) -> fmt::Result {
+        let _ = (checksum, to, options);
+        Ok(())
+    }
+
     /// Retrieves the underlying `FormattableLine` object.
     fn get_line(&self) -> &FormattableLine;
 
@@ -44,50 +131,231 @@ pub trait Line: Display + From<String> {
     ///
     /// If a given line is recognisable as the output of a
     /// hashing utility, its hash value will be coloured.
-    fn coloursum<I: BufRead, O: Write>(from: I, mut to: O) -> io::Result<()> {
-        for wrapped_line in from.lines() {
-            writeln!(to, "{}", Self::from(wrapped_line?))?
-        }
+    ///
+    /// Lines are handled as raw bytes, so lines which are not
+    /// valid UTF-8 are written out unchanged. Each line's original
+    /// terminator (`\r\n`, `\n`, or none at all at the end of the input)
+    /// is preserved exactly.
+    fn coloursum<I: BufRead, O: Write>(from: I, to: O) -> io::Result<()> {
+        Self::coloursum_with_options(from, to, &FormatOptions::default())
+    }
 
-        Ok(())
+    /// Takes each record in `from`, and writes it to `to`,
+    /// as configured by `options`.
+    ///
+    /// Behaves like `coloursum`, but records may be separated by something
+    /// other than line endings, such as the NUL bytes produced by
+    /// `sha256sum --zero`.
+    fn coloursum_with_options<I: BufRead, O: Write>(
+        from: I,
+        to: O,
+        options: &FormatOptions,
+    ) -> io::Result<()> {
+        coloursum(&LineFormatter::<Self>::new(), from, to, options)
+    }
+
+    /// Writes the processed line to the supplied `Write`r.
+    ///
+    /// Any bytes outside of the checksum are written exactly as they were read.
+    fn write_formatted<O: Write>(&self, to: &mut O) -> io::Result<()> {
+        self.write_formatted_with_options(to, &FormatOptions::default())
+    }
+
+    /// Writes the processed line to the supplied `Write`r,
+    /// as configured by `options`.
+    ///
+    /// Unless `options.unescape_filenames` is set, any bytes outside
+    /// of the checksum are written exactly as they were read.
+    fn write_formatted_with_options<O: Write>(
+        &self,
+        to: &mut O,
+        options: &FormatOptions,
+    ) -> io::Result<()> {
+        self.get_line()
+            .write_with(&LineFormatter::<Self>::new(), to, options)
     }
 
     /// Writes the processed line to the supplied `Formatter`.
     ///
+    /// Any bytes which are not valid UTF-8 are replaced with
+    /// `U+FFFD REPLACEMENT CHARACTER`; use `write_formatted` to
+    /// preserve them.
+    ///
     /// May be overridden in order to replace the checksum-replacing behaviour if necessary.
     fn to_formatted(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        let line = self.get_line();
+        self.to_formatted_with_options(formatter, &FormatOptions::default())
+    }
+
+    /// Writes the processed line to the supplied `fmt::Write`r,
+    /// as configured by `options`.
+    ///
+    /// Filenames are unescaped if `options.unescape_filenames` is set, as
+    /// with `write_formatted_with_options`. Any bytes which are not valid
+    /// UTF-8 are replaced with `U+FFFD REPLACEMENT CHARACTER`; use
+    /// `write_formatted_with_options` to preserve them.
+    fn to_formatted_with_options(
+        &self,
+        to: &mut dyn fmt::Write,
+        options: &FormatOptions,
+    ) -> fmt::Result {
+        let mut output = Vec::new();
+
+        // share `write_line` with the streaming path, so the two always agree
+        self.get_line()
+            .write_with(&LineFormatter::<Self>::new(), &mut output, options)
+            .map_err(|_| fmt::Error)?;
 
+        to.write_str(&String::from_utf8_lossy(&output))
+    }
+}
+
+/// Collects the output of a `write_hash` function into a `String`.
+///
+/// Useful for implementing `Line::format_hash` in terms of `Line::write_hash`.
+pub(crate) fn format_to_string(
+    hash: &str,
+    write_hash: impl FnOnce(&str, &mut dyn fmt::Write) -> fmt::Result,
+) -> String {
+    let mut formatted = String::new();
+
+    write_hash(hash, &mut formatted).expect("a formatter returned an error unexpectedly");
+
+    formatted
+}
+
+/// Decodes a base16 or base64 hash or digest into bytes, and writes
+/// those bytes to `to`, re-encoded by `encode`.
+///
+/// If the hash couldn't be decoded, or `encode` returns `None`,
+/// the original hash is written instead.
+pub(crate) fn write_reencoded(
+    hash: &str,
+    to: &mut dyn fmt::Write,
+    encode: impl FnOnce(&[u8]) -> Option<String>,
+) -> fmt::Result {
+    match decode_digest(hash).and_then(|bytes| encode(&bytes)) {
+        Some(encoded) => to.write_str(&encoded),
+        // if there was an error at any point, write the original value
+        None => to.write_str(hash),
+    }
+}
+
+/// Takes each record in `from`, and writes it to `to`,
+/// as configured by `options`.
+///
+/// If a given record is recognisable as the output of a hashing utility,
+/// its hash value will be formatted by `formatter`. This allows the
+/// formatter to be chosen at runtime, such as from a `FormatterRegistry`.
+///
+/// Formatters may also draw extra lines below a record, with
+/// `DigestFormatter::write_trailer`; these are separated by `\n`,
+/// whatever `options.record_separator` is, and come before the record's
+/// own terminator.
+///
+/// Records are read into a single buffer, and each hash is formatted into
+/// another, both of which are reused for every record; so as long as
+/// `formatter` itself doesn't allocate, neither does `coloursum`.
+pub fn coloursum<I: BufRead, O: Write>(
+    formatter: &dyn DigestFormatter,
+    mut from: I,
+    mut to: O,
+    options: &FormatOptions,
+) -> io::Result<()> {
+    let separator = options.record_separator;
+    let mut buffer = Vec::new();
+    let mut digest_buffer = String::new();
+
+    while from.read_until(separator.byte(), &mut buffer)? != 0 {
+        let (record, terminator) = split_terminator(&buffer, separator);
+
+        write_line(
+            record,
+            ChecksumSpans::find(record).as_ref(),
+            formatter,
+            &mut to,
+            options,
+            &mut digest_buffer,
+        )?;
+        to.write_all(terminator)?;
+
+        buffer.clear();
+    }
+
+    Ok(())
+}
+
+/// Writes `line` to `to`, with the hash found at `spans` formatted by
+/// `formatter` into `digest_buffer`, as configured by `options`.
+fn write_line(
+    line: &[u8],
+    spans: Option<&ChecksumSpans>,
+    formatter: &dyn DigestFormatter,
+    to: &mut dyn Write,
+    options: &FormatOptions,
+    digest_buffer: &mut String,
+) -> io::Result<()> {
+    let spans = match spans {
+        Some(spans) => spans,
         // Fall back to writing with no extra formatting
         // if we didn't detect a hash at any position
-        if line.formattable_start.is_none() && line.formattable_end.is_none() {
-            return write!(formatter, "{}", line.contents);
-        }
+        None => return to.write_all(line),
+    };
 
-        let slice_start = line.formattable_start.unwrap_or(0);
-        let slice_end = line.formattable_end.unwrap_or(line.contents.len());
+    let digest = spans.digest();
 
-        write!(
-            formatter,
-            "{}{}{}",
-            &line.contents[..slice_start],
-            Self::format_hash(line.contents[slice_start..slice_end].to_string()),
-            &line.contents[slice_end..],
+    // digests are only detected if they're made up of ASCII characters
+    let hash = match std::str::from_utf8(&line[digest.clone()]) {
+        Ok(hash) => hash,
+        Err(_) => return to.write_all(line),
+    };
+
+    digest_buffer.clear();
+    write_digest(formatter, hash, digest_buffer, options)
+        .map_err(|fmt::Error| io::Error::from(io::ErrorKind::Other))?;
+
+    if spans.is_escaped() && options.unescape_filenames {
+        // skip the escape marker, and decode the rest of the line
+        to.write_all(&unescape(&line[1..digest.start]))?;
+        to.write_all(digest_buffer.as_bytes())?;
+        to.write_all(&unescape(&line[digest.end..]))?;
+    } else {
+        to.write_all(&line[..digest.start])?;
+        to.write_all(digest_buffer.as_bytes())?;
+        to.write_all(&line[digest.end..])?;
+    }
+
+    // the digest has been written, so its buffer can be reused for the trailer
+    digest_buffer.clear();
+    formatter
+        .write_trailer(
+            &ChecksumLine::from_spans(line, spans.clone()),
+            digest_buffer,
+            options,
         )
+        .map_err(|fmt::Error| io::Error::from(io::ErrorKind::Other))?;
+
+    if !digest_buffer.is_empty() {
+        to.write_all(b"\n")?;
+        to.write_all(digest_buffer.as_bytes())?;
     }
-}
 
-/// Detects the *starting* offset of the
-/// hash in a BSD `md5(1)` style line
-fn find_bsd_tag_line(line: &str) -> Option<usize> {
-    let needle = " = ";
-    line.rfind(needle).map(|offset| offset + needle.len())
+    Ok(())
 }
 
-/// Detects the *ending* offset of the hash in a
-/// GNU `md5sum(1)` / perl `shasum(1)` style line
-fn find_sum_prefixed_line(line: &str) -> Option<usize> {
-    line.find("  ")
+/// Splits the terminator from the end of `record`.
+///
+/// For `RecordSeparator::Newline`, recognises `\r\n` and `\n` line endings.
+/// If `record` has no terminator, it is returned whole,
+/// along with an empty terminator.
+fn split_terminator(record: &[u8], separator: RecordSeparator) -> (&[u8], &'static [u8]) {
+    let terminator: &'static [u8] = match separator {
+        RecordSeparator::Newline if record.ends_with(b"\r\n") => b"\r\n",
+        RecordSeparator::Newline if record.ends_with(b"\n") => b"\n",
+        RecordSeparator::Nul if record.ends_with(b"\0") => b"\0",
+        _ => b"",
+    };
+
+    (&record[..record.len() - terminator.len()], terminator)
 }
 
 #[cfg(test)]
@@ -99,48 +367,232 @@ mod tests {
         let string = "MD5 (./src/main.rs) = b7527e0e28c09f6f62dd2d4197d5d225".to_string();
         let line = FormattableLine::from(string.clone());
 
-        assert_eq!(line.contents, string);
-        assert_eq!(line.formattable_start, Some(22));
-        assert_eq!(line.formattable_end, None);
+        assert_eq!(line.contents, string.into_bytes());
+
+        let checksum = line.checksum().unwrap();
+
+        assert_eq!(checksum.algorithm(), Some("MD5"));
+        assert_eq!(checksum.digest_range(), 22..54);
+        assert_eq!(checksum.separator(), None);
     }
 
     #[test]
-    fn find_bsd_tag_line_works() {
-        use super::find_bsd_tag_line;
+    fn from_binary_mode_line_works() {
+        use super::FormattableLine;
+        use crate::Separator;
 
+        let line =
+            FormattableLine::from("b7527e0e28c09f6f62dd2d4197d5d225 *./src/main.rs".to_string());
+
+        assert_eq!(line.checksum().unwrap().digest_range(), 0..32);
         assert_eq!(
-            find_bsd_tag_line("MD5 (./src/main.rs) = b7527e0e28c09f6f62dd2d4197d5d225"),
-            Some(22)
+            line.checksum().unwrap().separator(),
+            Some(Separator::Binary)
         );
+        assert!(line.is_binary());
+
+        let line =
+            FormattableLine::from("b7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs".to_string());
+
+        assert_eq!(line.checksum().unwrap().separator(), Some(Separator::Text));
+        assert!(!line.is_binary());
+
+        let line = FormattableLine::from("timeout = 30".to_string());
+
+        assert_eq!(line.checksum(), None);
+        assert!(!line.is_binary());
+    }
+
+    #[test]
+    fn from_bytes_keeps_non_utf8_contents() {
+        use super::FormattableLine;
+
+        let bytes = b"b7527e0e28c09f6f62dd2d4197d5d225  ./caf\xe9.txt".to_vec();
+        let line = FormattableLine::from(bytes.clone());
+
+        assert_eq!(line.contents, bytes);
+        assert_eq!(line.checksum().unwrap().digest_range(), 0..32);
+        assert_eq!(line.checksum().unwrap().path(), b"./caf\xe9.txt");
+    }
+
+    #[test]
+    fn coloursum_passes_through_non_utf8_filenames() {
+        use crate::{EcojiLine, Line};
+
+        let input: &[u8] = b"b7527e0e28c09f6f62dd2d4197d5d225  ./caf\xe9.txt\nMD5 (./src/main.rs) = b7527e0e28c09f6f62dd2d4197d5d225\n";
+        let mut output: Vec<u8> = Vec::new();
+
+        EcojiLine::coloursum(input, &mut output).unwrap();
+
         assert_eq!(
-            find_bsd_tag_line("b7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs"),
-            None
+            output,
+            [
+                "😨🏸🤰📺🙎📇🦎😨🍽🇮📆💣🍜☕☕☕  ./caf".as_bytes(),
+                b"\xe9.txt\n",
+                "MD5 (./src/main.rs) = 😨🏸🤰📺🙎📇🦎😨🍽🇮📆💣🍜☕☕☕\n".as_bytes(),
+            ]
+            .concat()
+        );
+    }
+
+    #[test]
+    fn split_terminator_works() {
+        use super::split_terminator;
+        use crate::RecordSeparator::{Newline, Nul};
+
+        assert_eq!(
+            split_terminator(
+                b"b7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs\r\n",
+                Newline
+            ),
+            (
+                &b"b7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs"[..],
+                &b"\r\n"[..]
+            )
         );
         assert_eq!(
-            find_bsd_tag_line(
-                "3e08ba70bfc57da75612af458c7ea94108f9a9ddf9d1bfd96de9c0e34e684bda  ./src/main.rs"
+            split_terminator(
+                b"b7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs\n",
+                Newline
             ),
-            None
+            (
+                &b"b7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs"[..],
+                &b"\n"[..]
+            )
+        );
+        assert_eq!(
+            split_terminator(b"b7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs", Newline),
+            (
+                &b"b7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs"[..],
+                &b""[..]
+            )
+        );
+        assert_eq!(split_terminator(b"\r", Newline), (&b"\r"[..], &b""[..]));
+        assert_eq!(
+            split_terminator(b"b7527e0e28c09f6f62dd2d4197d5d225  ./a\nb\0", Nul),
+            (&b"b7527e0e28c09f6f62dd2d4197d5d225  ./a\nb"[..], &b"\0"[..])
+        );
+        assert_eq!(
+            split_terminator(b"b7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs\n", Nul),
+            (
+                &b"b7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs\n"[..],
+                &b""[..]
+            )
+        );
+    }
+
+    #[test]
+    fn coloursum_with_options_splits_nul_records() {
+        use crate::{EcojiLine, FormatOptions, Line, RecordSeparator};
+
+        let input: &[u8] =
+            b"b7527e0e28c09f6f62dd2d4197d5d225  ./a\nb\0b7527e0e28c09f6f62dd2d4197d5d225  ./c\0";
+        let mut output: Vec<u8> = Vec::new();
+
+        EcojiLine::coloursum_with_options(
+            input,
+            &mut output,
+            &FormatOptions {
+                record_separator: RecordSeparator::Nul,
+                ..FormatOptions::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "😨🏸🤰📺🙎📇🦎😨🍽🇮📆💣🍜☕☕☕  ./a\nb\0😨🏸🤰📺🙎📇🦎😨🍽🇮📆💣🍜☕☕☕  ./c\0"
         );
     }
 
     #[test]
-    fn find_sum_prefixed_line_works() {
-        use super::find_sum_prefixed_line;
+    fn from_escaped_line_works() {
+        use super::FormattableLine;
+
+        let line = FormattableLine::from("\\b7527e0e28c09f6f62dd2d4197d5d225  ./a\\nb".to_string());
+
+        assert_eq!(line.checksum().unwrap().digest_range(), 1..33);
+        assert!(line.checksum().unwrap().is_escaped());
+
+        let line =
+            FormattableLine::from("\\MD5 (./a\\nb) = b7527e0e28c09f6f62dd2d4197d5d225".to_string());
+
+        assert_eq!(line.checksum().unwrap().digest_range(), 16..48);
+        assert!(line.checksum().unwrap().is_escaped());
+
+        let line = FormattableLine::from("\\not a checksum".to_string());
+
+        assert_eq!(line.checksum(), None);
+    }
+
+    #[test]
+    fn write_formatted_with_options_unescapes_filenames() {
+        use crate::{EcojiLine, FormatOptions, Line};
+
+        let options = FormatOptions {
+            unescape_filenames: true,
+            ..FormatOptions::default()
+        };
+
+        let mut output: Vec<u8> = Vec::new();
+        EcojiLine::from("\\b7527e0e28c09f6f62dd2d4197d5d225  ./a\\nb\\\\c".to_string())
+            .write_formatted_with_options(&mut output, &options)
+            .unwrap();
 
         assert_eq!(
-            find_sum_prefixed_line("b7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs"),
-            Some(32)
+            String::from_utf8(output).unwrap(),
+            "😨🏸🤰📺🙎📇🦎😨🍽🇮📆💣🍜☕☕☕  ./a\nb\\c"
         );
+
+        let mut output: Vec<u8> = Vec::new();
+        EcojiLine::from("\\MD5 (./a\\nb) = b7527e0e28c09f6f62dd2d4197d5d225".to_string())
+            .write_formatted_with_options(&mut output, &options)
+            .unwrap();
+
         assert_eq!(
-            find_sum_prefixed_line(
-                "3e08ba70bfc57da75612af458c7ea94108f9a9ddf9d1bfd96de9c0e34e684bda  ./src/main.rs"
-            ),
-            Some(64)
+            String::from_utf8(output).unwrap(),
+            "MD5 (./a\nb) = 😨🏸🤰📺🙎📇🦎😨🍽🇮📆💣🍜☕☕☕"
         );
+
+        // the `fmt::Write` path unescapes them too
+        let mut output = String::new();
+        EcojiLine::from("\\b7527e0e28c09f6f62dd2d4197d5d225  ./a\\nb\\\\c".to_string())
+            .to_formatted_with_options(&mut output, &options)
+            .unwrap();
+
+        assert_eq!(output, "😨🏸🤰📺🙎📇🦎😨🍽🇮📆💣🍜☕☕☕  ./a\nb\\c");
+
+        // escaped lines are left alone by default
+        let mut output: Vec<u8> = Vec::new();
+        EcojiLine::from("\\b7527e0e28c09f6f62dd2d4197d5d225  ./a\\nb".to_string())
+            .write_formatted(&mut output)
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "\\😨🏸🤰📺🙎📇🦎😨🍽🇮📆💣🍜☕☕☕  ./a\\nb"
+        );
+    }
+
+    #[test]
+    fn coloursum_works_with_dyn_formatters() {
+        use crate::{coloursum, FormatOptions, FormatterRegistry};
+
+        let registry = FormatterRegistry::default();
+        let input: &[u8] = b"b7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs\r\ntimeout = 30\n";
+        let mut output: Vec<u8> = Vec::new();
+
+        coloursum(
+            registry.get("ecoji").unwrap(),
+            input,
+            &mut output,
+            &FormatOptions::default(),
+        )
+        .unwrap();
+
         assert_eq!(
-            find_sum_prefixed_line("MD5 (./src/main.rs) = b7527e0e28c09f6f62dd2d4197d5d225"),
-            None
+            String::from_utf8(output).unwrap(),
+            "😨🏸🤰📺🙎📇🦎😨🍽🇮📆💣🍜☕☕☕  ./src/main.rs\r\ntimeout = 30\n"
         );
     }
 }
@@ -0,0 +1,300 @@
+use std::convert::From;
+use std::fmt;
+use std::fmt::Display;
+use std::marker::PhantomData;
+
+use base64::Engine;
+
+use crate::base_line::{format_to_string, write_reencoded, FormattableLine, Line};
+
+/// The alphabet Nix uses for its base32 hashes, which leaves out
+/// `e`, `o`, `u` and `t`, to avoid spelling words.
+const NIX_BASE32_ALPHABET: &[u8; 32] = b"0123456789abcdfghijklmnpqrsvwxyz";
+
+/// The alphabet Bitcoin and IPFS use for base58, which leaves out
+/// `0`, `O`, `I` and `l`, as they're easily mistaken for each other.
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// The z-base-32 alphabet, ordered so the easiest characters
+/// to read, write and say come up most often.
+const Z_BASE32_ALPHABET: &[u8; 32] = b"ybndrfg8ejkmcpqxot1uwisza345h769";
+
+/// A way of re-encoding the bytes of a digest, for `ReencodedLine`.
+pub trait Encoding {
+    /// Encodes `bytes`.
+    fn encode(bytes: &[u8]) -> String;
+}
+
+/// Line with the digest decoded from base16 or base64,
+/// and re-encoded with the `Encoding` `E`.
+pub struct ReencodedLine<E: Encoding>(FormattableLine, PhantomData<fn() -> E>);
+impl<E: Encoding> From<String> for ReencodedLine<E> {
+    fn from(contents: String) -> Self {
+        Self(FormattableLine::from(contents), PhantomData)
+    }
+}
+
+impl<E: Encoding> From<Vec<u8>> for ReencodedLine<E> {
+    fn from(contents: Vec<u8>) -> Self {
+        Self(FormattableLine::from(contents), PhantomData)
+    }
+}
+
+impl<E: Encoding> fmt::Debug for ReencodedLine<E> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_tuple(std::any::type_name::<Self>())
+            .field(&self.0)
+            .finish()
+    }
+}
+
+impl<E: Encoding> Display for ReencodedLine<E> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.to_formatted(formatter)
+    }
+}
+
+impl<E: Encoding> Line for ReencodedLine<E> {
+    fn get_line(&self) -> &FormattableLine {
+        &self.0
+    }
+
+    fn format_hash(hash: String) -> String {
+        format_to_string(&hash, Self::write_hash)
+    }
+
+    /// Formats a base16 or base64-format hash or digest.
+    ///
+    /// Data will be re-encoded with `E`.
+    fn write_hash(hash: &str, to: &mut dyn fmt::Write) -> fmt::Result {
+        write_reencoded(hash, to, |bytes| Some(E::encode(bytes)))
+    }
+}
+
+#[derive(Debug)]
+/// Standard, padded base64, as used by Subresource Integrity and npm's
+/// `integrity` fields; without the algorithm prefix, such as `sha256-`,
+/// which integrity strings add.
+pub struct Base64Encoding;
+impl Encoding for Base64Encoding {
+    fn encode(bytes: &[u8]) -> String {
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    }
+}
+
+#[derive(Debug)]
+/// Nix's flavour of base32, as used in Nix store paths and
+/// `nix-hash --to-base32`, which, unlike RFC 4648's, starts from
+/// the end of the data, and uses its own alphabet.
+pub struct NixBase32Encoding;
+impl Encoding for NixBase32Encoding {
+    fn encode(bytes: &[u8]) -> String {
+        encode_nix_base32(bytes)
+    }
+}
+
+#[derive(Debug)]
+/// Base58 with Bitcoin's alphabet, as used by IPFS and cryptocurrencies.
+///
+/// Only the digest is encoded, so this won't match IPFS's `Qm…`
+/// identifiers, which begin with a multihash header.
+pub struct Base58Encoding;
+impl Encoding for Base58Encoding {
+    fn encode(bytes: &[u8]) -> String {
+        encode_base58(bytes)
+    }
+}
+
+#[derive(Debug)]
+/// z-base-32, a base32 alphabet designed to be easy for people to read
+/// and type, five bits at a time, starting from the most significant bits,
+/// without any padding.
+pub struct ZBase32Encoding;
+impl Encoding for ZBase32Encoding {
+    fn encode(bytes: &[u8]) -> String {
+        encode_z_base32(bytes)
+    }
+}
+
+/// Line with the digest re-encoded in standard, padded base64.
+pub type Base64Line = ReencodedLine<Base64Encoding>;
+
+/// Line with the digest re-encoded in Nix's flavour of base32.
+pub type NixBase32Line = ReencodedLine<NixBase32Encoding>;
+
+/// Line with the digest re-encoded in base58.
+pub type Base58Line = ReencodedLine<Base58Encoding>;
+
+/// Line with the digest re-encoded in z-base-32.
+pub type ZBase32Line = ReencodedLine<ZBase32Encoding>;
+
+/// Encodes `bytes` as Nix's base32.
+///
+/// The last character holds the lowest five bits of the first byte,
+/// and the first character holds whatever's left at the end.
+fn encode_nix_base32(bytes: &[u8]) -> String {
+    let length = match bytes.len() {
+        0 => 0,
+        byte_length => (byte_length * 8 - 1) / 5 + 1,
+    };
+
+    (0..length)
+        .rev()
+        .map(|index| {
+            let bit = index * 5;
+            let (byte, shift) = (bit / 8, bit % 8);
+
+            let low = u16::from(bytes[byte]) >> shift;
+            let high = bytes
+                .get(byte + 1)
+                .map_or(0, |&next| u16::from(next) << (8 - shift));
+
+            char::from(NIX_BASE32_ALPHABET[usize::from((low | high) & 0x1f)])
+        })
+        .collect()
+}
+
+/// Encodes `bytes` as base58, treating them as one big-endian number.
+///
+/// Each leading zero byte is written as a leading `1`.
+fn encode_base58(bytes: &[u8]) -> String {
+    // the digits of the number so far, least significant first
+    let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 138 / 100 + 1);
+
+    for &byte in bytes {
+        let mut carry = u32::from(byte);
+        for digit in digits.iter_mut() {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    let zeros = bytes.iter().take_while(|&&byte| byte == 0).count();
+
+    let mut encoded = "1".repeat(zeros);
+    encoded.extend(
+        digits
+            .iter()
+            .rev()
+            .map(|&digit| char::from(BASE58_ALPHABET[usize::from(digit)])),
+    );
+
+    encoded
+}
+
+/// Encodes `bytes` as z-base-32, filling out the last character
+/// with zero bits.
+fn encode_z_base32(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len() * 8 / 5 + 1);
+    let mut buffer: u16 = 0;
+    let mut buffered_bits = 0;
+
+    for &byte in bytes {
+        buffer = (buffer << 8) | u16::from(byte);
+        buffered_bits += 8;
+
+        while buffered_bits >= 5 {
+            buffered_bits -= 5;
+            encoded.push(char::from(
+                Z_BASE32_ALPHABET[usize::from((buffer >> buffered_bits) & 0x1f)],
+            ));
+        }
+
+        // only the bits which haven't been written yet need keeping
+        buffer &= (1 << buffered_bits) - 1;
+    }
+
+    if buffered_bits > 0 {
+        encoded.push(char::from(
+            Z_BASE32_ALPHABET[usize::from((buffer << (5 - buffered_bits)) & 0x1f)],
+        ));
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn display_works() {
+        use super::{Base58Line, Base64Line, NixBase32Line, ZBase32Line};
+
+        let line = "MD5 (./src/main.rs) = b7527e0e28c09f6f62dd2d4197d5d225";
+
+        assert_eq!(
+            format!("{}", Base64Line::from(line.to_string())),
+            "MD5 (./src/main.rs) = t1J+DijAn29i3S1Bl9XSJQ=="
+        );
+        assert_eq!(
+            format!("{}", NixBase32Line::from(line.to_string())),
+            "MD5 (./src/main.rs) = 15sbarfh9dvmi6z7y05077wlmp"
+        );
+        assert_eq!(
+            format!("{}", Base58Line::from(line.to_string())),
+            "MD5 (./src/main.rs) = PdyFakKbX6x7EcRsVjqLQc"
+        );
+        assert_eq!(
+            format!("{}", ZBase32Line::from(line.to_string())),
+            "MD5 (./src/main.rs) = s7j8hdteanxs6as7fiy3xiq1rw"
+        );
+    }
+
+    #[test]
+    fn format_hash_works() {
+        use super::{Base64Line, NixBase32Line};
+        use crate::Line;
+
+        // the SHA-256 digest of no data at all
+        let hash = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+        assert_eq!(
+            Base64Line::format_hash(hash.to_string()),
+            "47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU="
+        );
+        assert_eq!(
+            NixBase32Line::format_hash(hash.to_string()),
+            "0mdqa9w1p6cmli6976v4wi0sw9r4p5prkj7lzfd1877wk11c9c73"
+        );
+        // base64 digests are re-encoded too
+        assert_eq!(
+            NixBase32Line::format_hash("47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=".to_string()),
+            "0mdqa9w1p6cmli6976v4wi0sw9r4p5prkj7lzfd1877wk11c9c73"
+        );
+    }
+
+    #[test]
+    fn encode_base58_works() {
+        use super::encode_base58;
+
+        assert_eq!(encode_base58(b""), "");
+        assert_eq!(encode_base58(b"Hello World!"), "2NEpo7TZRRrLZSi2U");
+        assert_eq!(encode_base58(&[0, 0, 1]), "112");
+    }
+
+    #[test]
+    fn encode_z_base32_works() {
+        use super::encode_z_base32;
+
+        // examples from the z-base-32 specification
+        assert_eq!(encode_z_base32(&[0xf0, 0xbf, 0xc7]), "6n9hq");
+        assert_eq!(encode_z_base32(&[0xd4, 0x7a, 0x04]), "4t7ye");
+        assert_eq!(encode_z_base32(&[]), "");
+    }
+
+    #[test]
+    fn format_hash_doesnt_crash_on_non_base16_characters() {
+        use super::{Base58Line, Base64Line, NixBase32Line, ZBase32Line};
+        use crate::Line;
+
+        assert_eq!(Base64Line::format_hash("😄".to_string()), "😄");
+        assert_eq!(NixBase32Line::format_hash("😄".to_string()), "😄");
+        assert_eq!(Base58Line::format_hash("😄".to_string()), "😄");
+        assert_eq!(ZBase32Line::format_hash("😄".to_string()), "😄");
+    }
+}
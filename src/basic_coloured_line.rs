@@ -0,0 +1,138 @@
+use std::convert::From;
+use std::fmt;
+use std::fmt::Display;
+
+use crate::base_line::{format_to_string, FormattableLine, Line};
+use crate::colour_support::ColourSupport;
+use crate::format_options::FormatOptions;
+
+/// SGR parameters for each hexadecimal digit's value.
+///
+/// Values 1 to 6 use the basic ANSI colours, and 9 to 14 use the same
+/// colours in bold (which most terminals also show brighter). Black text
+/// can't be seen on dark themes, nor white text on light ones, so 0 and 8
+/// use reverse video instead, and 7 and 15 use grey (bright black).
+const NIBBLE_STYLES: [&str; 16] = [
+    "7", "31", "32", "33", "34", "35", "36", "90", "1;7", "1;31", "1;32", "1;33", "1;34", "1;35",
+    "1;36", "1;90",
+];
+
+#[derive(Debug)]
+/// Line with formatting which uses only the 16 basic ANSI colours,
+/// for terminals which can't display the xterm 256 colour palette.
+pub struct BasicColouredLine(FormattableLine);
+impl From<String> for BasicColouredLine {
+    fn from(contents: String) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl From<Vec<u8>> for BasicColouredLine {
+    fn from(contents: Vec<u8>) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl Display for BasicColouredLine {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.to_formatted(formatter)
+    }
+}
+
+impl Line for BasicColouredLine {
+    fn get_line(&self) -> &FormattableLine {
+        &self.0
+    }
+
+    fn format_hash(hash: String) -> String {
+        format_to_string(&hash, Self::write_hash)
+    }
+
+    /// Formats a base16-format hash or digest.
+    ///
+    /// Each hexadecimal digit will be styled according to its value, using
+    /// one of 7 colours or reverse video, in bold or not, and every other
+    /// 8-bit byte is underlined, so that the bytes' boundaries can be seen.
+    fn write_hash(hash: &str, to: &mut dyn fmt::Write) -> fmt::Result {
+        Self::write_hash_with_options(hash, to, &FormatOptions::default())
+    }
+
+    /// Formats a base16-format hash or digest.
+    ///
+    /// Behaves like `write_hash`, unless `options.colour_support`
+    /// is `ColourSupport::None`, in which case the hash is left as it is.
+    fn write_hash_with_options(
+        hash: &str,
+        to: &mut dyn fmt::Write,
+        options: &FormatOptions,
+    ) -> fmt::Result {
+        // if there are any non-base16 characters, or colours
+        // aren't supported at all, write the original value
+        if options.colour_support == ColourSupport::None
+            || !hash.bytes().all(|character| character.is_ascii_hexdigit())
+        {
+            return to.write_str(hash);
+        }
+
+        for (offset, character) in hash.char_indices() {
+            // these are all base16 characters, so this can't fail
+            let nibble = character.to_digit(16).unwrap_or_default() as usize;
+
+            to.write_str("\x1b[")?;
+            // each byte is two characters long
+            if offset / 2 % 2 == 1 {
+                to.write_str("4;")?;
+            }
+            to.write_str(NIBBLE_STYLES[nibble])?;
+            to.write_char('m')?;
+            to.write_char(character)?;
+            to.write_str("\x1b[0m")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn display_works() {
+        use super::BasicColouredLine;
+
+        assert_eq!(
+            format!("{}", BasicColouredLine::from("MD5 (./src/main.rs) = b7527e0e28c09f6f62dd2d4197d5d225".to_string())),
+            "MD5 (./src/main.rs) = \u{1b}[1;33mb\u{1b}[0m\u{1b}[90m7\u{1b}[0m\u{1b}[4;35m5\u{1b}[0m\u{1b}[4;32m2\u{1b}[0m\u{1b}[90m7\u{1b}[0m\u{1b}[1;36me\u{1b}[0m\u{1b}[4;7m0\u{1b}[0m\u{1b}[4;1;36me\u{1b}[0m\u{1b}[32m2\u{1b}[0m\u{1b}[1;7m8\u{1b}[0m\u{1b}[4;1;34mc\u{1b}[0m\u{1b}[4;7m0\u{1b}[0m\u{1b}[1;31m9\u{1b}[0m\u{1b}[1;90mf\u{1b}[0m\u{1b}[4;36m6\u{1b}[0m\u{1b}[4;1;90mf\u{1b}[0m\u{1b}[36m6\u{1b}[0m\u{1b}[32m2\u{1b}[0m\u{1b}[4;1;35md\u{1b}[0m\u{1b}[4;1;35md\u{1b}[0m\u{1b}[32m2\u{1b}[0m\u{1b}[1;35md\u{1b}[0m\u{1b}[4;34m4\u{1b}[0m\u{1b}[4;31m1\u{1b}[0m\u{1b}[1;31m9\u{1b}[0m\u{1b}[90m7\u{1b}[0m\u{1b}[4;1;35md\u{1b}[0m\u{1b}[4;35m5\u{1b}[0m\u{1b}[1;35md\u{1b}[0m\u{1b}[32m2\u{1b}[0m\u{1b}[4;32m2\u{1b}[0m\u{1b}[4;35m5\u{1b}[0m"
+        )
+    }
+
+    #[test]
+    fn format_hash_works() {
+        use super::BasicColouredLine;
+        use crate::Line;
+
+        assert_eq!(
+            BasicColouredLine::format_hash("08f7".to_string()),
+            "\u{1b}[7m0\u{1b}[0m\u{1b}[1;7m8\u{1b}[0m\u{1b}[4;1;90mf\u{1b}[0m\u{1b}[4;90m7\u{1b}[0m"
+        );
+    }
+
+    #[test]
+    fn format_hash_doesnt_crash_on_non_base16_characters() {
+        use super::BasicColouredLine;
+        use crate::Line;
+
+        assert_eq!(BasicColouredLine::format_hash("ASDF".to_string()), "ASDF");
+        assert_eq!(BasicColouredLine::format_hash("😄".to_string()), "😄");
+    }
+
+    #[test]
+    fn nibbles_have_distinct_styles() {
+        use super::NIBBLE_STYLES;
+        use std::collections::HashSet;
+
+        assert_eq!(
+            NIBBLE_STYLES.iter().collect::<HashSet<_>>().len(),
+            NIBBLE_STYLES.len()
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use std::convert::From;
+use std::fmt;
+use std::fmt::Display;
+
+use crate::base_line::{format_to_string, FormattableLine, Line};
+use crate::bip39_english::WORDS;
+use crate::checksum_line::decode_digest;
+use crate::format_options::FormatOptions;
+use crate::units::write_units;
+
+/// How many bits of the digest each word encodes.
+const BITS_PER_WORD: u32 = 11;
+
+#[derive(Debug)]
+/// Line with the digest spelled out in words from the BIP-39 English
+/// wordlist, which are easier to compare reliably than hexadecimal.
+pub struct BIP39WordLine(FormattableLine);
+impl From<String> for BIP39WordLine {
+    fn from(contents: String) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl From<Vec<u8>> for BIP39WordLine {
+    fn from(contents: Vec<u8>) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl Display for BIP39WordLine {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.to_formatted(formatter)
+    }
+}
+
+impl Line for BIP39WordLine {
+    fn get_line(&self) -> &FormattableLine {
+        &self.0
+    }
+
+    fn format_hash(hash: String) -> String {
+        format_to_string(&hash, Self::write_hash)
+    }
+
+    /// Formats a base16 or base64-format hash or digest.
+    ///
+    /// The digest's bits are split into groups of 11, most significant
+    /// first, and each group is written as the word at that position in
+    /// the BIP-39 English wordlist. If the digest's length isn't a multiple
+    /// of 11 bits, the last group is padded with zero bits.
+    ///
+    /// Unlike a BIP-39 mnemonic, no checksum is added, so the words for
+    /// a 128-bit digest aren't a valid mnemonic, and can't be used as one.
+    fn write_hash(hash: &str, to: &mut dyn fmt::Write) -> fmt::Result {
+        Self::write_hash_with_options(hash, to, &FormatOptions::default())
+    }
+
+    /// Formats a base16 or base64-format hash or digest.
+    ///
+    /// The digest will be spelled out in BIP-39 words, separated by spaces.
+    /// If `options.grouping` is set, its separator is written between
+    /// each group of that many words.
+    fn write_hash_with_options(
+        hash: &str,
+        to: &mut dyn fmt::Write,
+        options: &FormatOptions,
+    ) -> fmt::Result {
+        match decode_digest(hash) {
+            Some(bytes) => write_units(hash, &words(&bytes), " ", to, options),
+            // if there was an error at any point, write the original value
+            None => to.write_str(hash),
+        }
+    }
+
+    fn writes_units() -> bool {
+        true
+    }
+}
+
+/// Spells out `bytes` as BIP-39 words.
+fn words(bytes: &[u8]) -> Vec<&'static str> {
+    let mut words = Vec::with_capacity(bytes.len() * 8 / BITS_PER_WORD as usize + 1);
+
+    // the bits which haven't been written yet, and how many there are
+    let mut pending: u32 = 0;
+    let mut pending_bits = 0;
+
+    for &byte in bytes {
+        pending = (pending << 8) | u32::from(byte);
+        pending_bits += 8;
+
+        if pending_bits >= BITS_PER_WORD {
+            pending_bits -= BITS_PER_WORD;
+            words.push(WORDS[(pending >> pending_bits) as usize]);
+            pending &= (1 << pending_bits) - 1;
+        }
+    }
+
+    // pad any leftover bits with zeroes, to fill one last word
+    if pending_bits > 0 {
+        words.push(WORDS[(pending << (BITS_PER_WORD - pending_bits)) as usize]);
+    }
+
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn display_works() {
+        use super::BIP39WordLine;
+
+        assert_eq!(
+            format!(
+                "{}",
+                BIP39WordLine::from(
+                    "MD5 (./src/main.rs) = b7527e0e28c09f6f62dd2d4197d5d225".to_string()
+                )
+            ),
+            "MD5 (./src/main.rs) = resemble negative logic face antique response merge pizza dose salt innocent energy"
+        )
+    }
+
+    #[test]
+    fn format_hash_works() {
+        use super::BIP39WordLine;
+        use crate::Line;
+
+        // the same words as the BIP-39 test vector, apart from the last,
+        // which is padded rather than including a checksum
+        assert_eq!(
+            BIP39WordLine::format_hash("7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f".to_string()),
+            "legal winner thank year wave sausage worth useful legal winner thank wrap"
+        );
+        assert_eq!(
+            BIP39WordLine::format_hash(
+                "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08".to_string()
+            ),
+            "panel custom call awesome sick ready hamster wool patch client reduce clip desk pole hole gesture lion grief firm subway force job choice abandon"
+        );
+        assert_eq!(BIP39WordLine::format_hash("ff".to_string()), "yellow");
+        assert_eq!(
+            BIP39WordLine::format_hash("8040".to_string()),
+            "leopard abandon"
+        );
+
+        // base64 digests are decoded too
+        assert_eq!(
+            BIP39WordLine::format_hash("t1J+DijAn29i3S1Bl9XSJQ==".to_string()),
+            BIP39WordLine::format_hash("b7527e0e28c09f6f62dd2d4197d5d225".to_string())
+        );
+    }
+
+    #[test]
+    fn format_hash_doesnt_crash_on_non_base16_characters() {
+        use super::BIP39WordLine;
+        use crate::Line;
+
+        assert_eq!(BIP39WordLine::format_hash("ASD!".to_string()), "ASD!");
+        assert_eq!(BIP39WordLine::format_hash("😄".to_string()), "😄");
+    }
+}
@@ -0,0 +1,180 @@
+use std::convert::From;
+use std::fmt;
+use std::fmt::Display;
+
+use crate::base_line::{format_to_string, FormattableLine, Line};
+use crate::checksum_line::decode_digest;
+use crate::format_options::FormatOptions;
+use crate::units::write_units;
+
+const VOWELS: &[u8; 6] = b"aeiouy";
+const CONSONANTS: &[u8; 17] = b"bcdfghklmnprstvzx";
+
+#[derive(Debug)]
+/// Line with Bubble Babble encoding, as used by OpenSSH's `ssh-keygen -B`.
+pub struct BubbleBabbleLine(FormattableLine);
+impl From<String> for BubbleBabbleLine {
+    fn from(contents: String) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl From<Vec<u8>> for BubbleBabbleLine {
+    fn from(contents: Vec<u8>) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl Display for BubbleBabbleLine {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.to_formatted(formatter)
+    }
+}
+
+impl Line for BubbleBabbleLine {
+    fn get_line(&self) -> &FormattableLine {
+        &self.0
+    }
+
+    fn format_hash(hash: String) -> String {
+        format_to_string(&hash, Self::write_hash)
+    }
+
+    /// Formats a base16 or base64-format hash or digest.
+    ///
+    /// Data will be encoded using Antti Huima's Bubble Babble encoding,
+    /// which turns each pair of bytes into a pronounceable, five letter
+    /// "word", with a checksum mixed into the vowels.
+    fn write_hash(hash: &str, to: &mut dyn fmt::Write) -> fmt::Result {
+        Self::write_hash_with_options(hash, to, &FormatOptions::default())
+    }
+
+    /// Formats a base16 or base64-format hash or digest.
+    ///
+    /// Data will be encoded using Bubble Babble. If `options.grouping`
+    /// is set, its separator replaces the dash after each group
+    /// of that many five letter words.
+    fn write_hash_with_options(
+        hash: &str,
+        to: &mut dyn fmt::Write,
+        options: &FormatOptions,
+    ) -> fmt::Result {
+        let bytes = match decode_digest(hash) {
+            Some(bytes) => bytes,
+            // if there was an error at any point, write the original value
+            None => return to.write_str(hash),
+        };
+
+        let mut encoded = String::new();
+        write_bubble_babble(&bytes, &mut encoded)?;
+
+        let words: Vec<&str> = encoded.split('-').collect();
+        write_units(hash, &words, "-", to, options)
+    }
+
+    fn writes_units() -> bool {
+        true
+    }
+}
+
+/// Writes `bytes`, Bubble Babble encoded, to `to`.
+fn write_bubble_babble(bytes: &[u8], to: &mut dyn fmt::Write) -> fmt::Result {
+    let vowel = |index: usize| char::from(VOWELS[index % 6]);
+    let consonant = |index: usize| char::from(CONSONANTS[index]);
+
+    // the vowels around each byte's consonant encode its first and last
+    // two bits, offset by a checksum of the bytes before it
+    let write_byte = |byte: usize, seed: usize, to: &mut dyn fmt::Write| {
+        to.write_char(vowel((byte >> 6) + seed))?;
+        to.write_char(consonant((byte >> 2) & 15))?;
+        to.write_char(vowel((byte & 3) + seed / 6))
+    };
+
+    let mut seed = 1;
+    let pairs = bytes.chunks_exact(2);
+    let remainder = pairs.remainder();
+
+    to.write_char('x')?;
+
+    for pair in pairs {
+        let (first, second) = (usize::from(pair[0]), usize::from(pair[1]));
+
+        write_byte(first, seed, to)?;
+        to.write_char(consonant(second >> 4))?;
+        to.write_char('-')?;
+        to.write_char(consonant(second & 15))?;
+
+        seed = (seed * 5 + first * 7 + second) % 36;
+    }
+
+    match remainder {
+        [last] => write_byte(usize::from(*last), seed, to)?,
+        // with an even number of bytes, the checksum is written on its own
+        _ => {
+            to.write_char(vowel(seed))?;
+            to.write_char('x')?;
+            to.write_char(vowel(seed / 6))?;
+        }
+    }
+
+    to.write_char('x')
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn display_works() {
+        use super::BubbleBabbleLine;
+
+        assert_eq!(
+            format!(
+                "{}",
+                BubbleBabbleLine::from(
+                    "b7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs".to_string()
+                )
+            ),
+            "xotoh-dezib-vipys-bulak-zymat-toreg-cehit-higid-hixox  ./src/main.rs"
+        )
+    }
+
+    #[test]
+    fn format_hash_works() {
+        use super::BubbleBabbleLine;
+        use crate::Line;
+
+        assert_eq!(
+            BubbleBabbleLine::format_hash("cc6917b830dae305766d1d72d7bd9fdc673272b2".to_string()),
+            "xufak-niher-meset-pumyb-hutik-tilol-daher-telet-sonof-disar-doxox"
+        );
+        // base64 digests are decoded too
+        assert_eq!(
+            BubbleBabbleLine::format_hash("zGkXuDDa4wV2bR1y172f3GcycrI=".to_string()),
+            "xufak-niher-meset-pumyb-hutik-tilol-daher-telet-sonof-disar-doxox"
+        );
+    }
+
+    #[test]
+    fn write_bubble_babble_works() {
+        use super::write_bubble_babble;
+
+        // the test vectors from the Bubble Babble specification
+        let encode = |bytes: &[u8]| {
+            let mut output = String::new();
+            write_bubble_babble(bytes, &mut output).unwrap();
+            output
+        };
+
+        assert_eq!(encode(b""), "xexax");
+        assert_eq!(encode(b"1234567890"), "xesef-disof-gytuf-katof-movif-baxux");
+        assert_eq!(encode(b"Pineapple"), "xigak-nyryk-humil-bosek-sonax");
+    }
+
+    #[test]
+    fn format_hash_doesnt_crash_on_non_base16_characters() {
+        use super::BubbleBabbleLine;
+        use crate::Line;
+
+        assert_eq!(BubbleBabbleLine::format_hash("ASD!".to_string()), "ASD!");
+        assert_eq!(BubbleBabbleLine::format_hash("😄".to_string()), "😄");
+    }
+}
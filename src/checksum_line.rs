@@ -0,0 +1,712 @@
+use std::borrow::Cow;
+use std::ops::Range;
+
+use base64::Engine;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The style of a line of checksum utility output.
+pub enum LineStyle {
+    /// A BSD `md5(1)` "tag" style line, such as
+    /// `MD5 (./src/main.rs) = b7527e0e28c09f6f62dd2d4197d5d225`.
+    BsdTag,
+    /// A BSD "tag" style line whose path GNU Coreutils has escaped,
+    /// marked with a leading backslash.
+    EscapedBsdTag,
+    /// A GNU `md5sum(1)` / perl `shasum(1)` style line, such as
+    /// `b7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs`.
+    Gnu,
+    /// A GNU style line whose filename GNU Coreutils has escaped,
+    /// marked with a leading backslash.
+    EscapedGnu,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The separator between the hash and the filename
+/// in a GNU `md5sum(1)` / perl `shasum(1)` style line.
+pub enum Separator {
+    /// Two spaces; a space followed by the text mode marker.
+    Text,
+    /// A space followed by the `*` binary mode marker.
+    Binary,
+    /// A single space, with no mode marker,
+    /// as used by some other checksum utilities.
+    Space,
+}
+
+impl Separator {
+    /// The length of the separator, in bytes.
+    fn len(self) -> usize {
+        match self {
+            Separator::Text | Separator::Binary => 2,
+            Separator::Space => 1,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// The positions of each part of a line of checksum utility output.
+///
+/// Kept separately from the line itself, so that `FormattableLine`
+/// can hold on to them alongside the line's contents.
+pub(crate) struct ChecksumSpans {
+    style: LineStyle,
+    algorithm: Option<Range<usize>>,
+    path: Range<usize>,
+    digest: Range<usize>,
+    separator: Option<Separator>,
+}
+
+impl ChecksumSpans {
+    /// Detects the parts of a line of checksum utility output.
+    pub(crate) fn find(line: &[u8]) -> Option<Self> {
+        // GNU Coreutils prefixes lines with a backslash
+        // if it had to escape characters in the filename
+        let escaped = line.first() == Some(&b'\\');
+
+        // skip over the escape marker, if present
+        let offset = usize::from(escaped);
+        let unescaped = &line[offset..];
+
+        if let Some(suffix_start) = find_bsd_tag_line(unescaped) {
+            // `find_bsd_tag_line` has already verified these are present
+            let algorithm_end = find_bytes(unescaped, b" (")?;
+            let path_end = suffix_start - b") = ".len();
+
+            return Some(Self {
+                style: if escaped {
+                    LineStyle::EscapedBsdTag
+                } else {
+                    LineStyle::BsdTag
+                },
+                algorithm: Some(offset..algorithm_end + offset),
+                path: algorithm_end + 2 + offset..path_end + offset,
+                digest: suffix_start + offset..line.len(),
+                separator: None,
+            });
+        }
+
+        let (prefix_end, separator) = find_sum_prefixed_line(unescaped)?;
+
+        Some(Self {
+            style: if escaped {
+                LineStyle::EscapedGnu
+            } else {
+                LineStyle::Gnu
+            },
+            algorithm: None,
+            path: prefix_end + separator.len() + offset..line.len(),
+            digest: offset..prefix_end + offset,
+            separator: Some(separator),
+        })
+    }
+
+    /// The range of the digest within the line.
+    pub(crate) fn digest(&self) -> Range<usize> {
+        self.digest.clone()
+    }
+
+    /// Whether the line was escaped by GNU Coreutils.
+    pub(crate) fn is_escaped(&self) -> bool {
+        matches!(self.style, LineStyle::EscapedBsdTag | LineStyle::EscapedGnu)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// A line of checksum utility output, parsed into its parts.
+///
+/// ```rust
+/// use coloursum::{ChecksumLine, LineStyle};
+///
+/// let line = ChecksumLine::parse(b"SHA1 (./src/main.rs) = 6b8e7c1c3ab4e6a26b42fcf5e4b55bfcf5d3ac1f").unwrap();
+///
+/// assert_eq!(line.style(), LineStyle::BsdTag);
+/// assert_eq!(line.algorithm(), Some("SHA1"));
+/// assert_eq!(line.path(), b"./src/main.rs");
+/// assert_eq!(line.digest(), "6b8e7c1c3ab4e6a26b42fcf5e4b55bfcf5d3ac1f");
+/// assert_eq!(line.digest_range(), 23..63);
+/// assert_eq!(line.digest_bytes().unwrap()[..4], [0x6b, 0x8e, 0x7c, 0x1c]);
+/// assert!(!line.is_binary());
+/// ```
+pub struct ChecksumLine<'a> {
+    line: &'a [u8],
+    spans: ChecksumSpans,
+}
+
+impl<'a> ChecksumLine<'a> {
+    /// Parses a line of checksum utility output, without its line ending.
+    ///
+    /// Returns `None` if the line isn't recognisable as
+    /// the output of a checksum utility.
+    pub fn parse(line: &'a [u8]) -> Option<Self> {
+        ChecksumSpans::find(line).map(|spans| Self::from_spans(line, spans))
+    }
+
+    /// Reassembles a `ChecksumLine` from the spans previously found in `line`.
+    pub(crate) fn from_spans(line: &'a [u8], spans: ChecksumSpans) -> Self {
+        Self { line, spans }
+    }
+
+    /// The whole line, as it was parsed.
+    pub fn line(&self) -> &'a [u8] {
+        self.line
+    }
+
+    /// The style of the line.
+    pub fn style(&self) -> LineStyle {
+        self.spans.style
+    }
+
+    /// The name of the algorithm used, for BSD "tag" style lines.
+    pub fn algorithm(&self) -> Option<&'a str> {
+        let range = self.spans.algorithm.clone()?;
+
+        // algorithm names are only detected if they're known, ASCII names
+        std::str::from_utf8(&self.line[range]).ok()
+    }
+
+    /// The path of the file, exactly as it appears in the line.
+    ///
+    /// For escaped lines, this is the escaped form of the path.
+    pub fn path(&self) -> &'a [u8] {
+        &self.line[self.spans.path.clone()]
+    }
+
+    /// The path of the file, with any GNU Coreutils escape sequences decoded.
+    pub fn unescaped_path(&self) -> Cow<'a, [u8]> {
+        if self.is_escaped() {
+            Cow::Owned(unescape(self.path()))
+        } else {
+            Cow::Borrowed(self.path())
+        }
+    }
+
+    /// The digest, as it appears in the line.
+    pub fn digest(&self) -> &'a str {
+        // digests are only detected if they're made up of ASCII characters
+        std::str::from_utf8(&self.line[self.spans.digest.clone()]).unwrap_or_default()
+    }
+
+    /// The range of the digest within the line.
+    pub fn digest_range(&self) -> Range<usize> {
+        self.spans.digest()
+    }
+
+    /// The digest, decoded from base16 or base64 into bytes.
+    ///
+    /// Returns `None` if the digest couldn't be decoded.
+    pub fn digest_bytes(&self) -> Option<Vec<u8>> {
+        decode_digest(self.digest())
+    }
+
+    /// The separator between the hash and the filename, for GNU style lines.
+    pub fn separator(&self) -> Option<Separator> {
+        self.spans.separator
+    }
+
+    /// Whether the line was produced in binary mode; that is, whether
+    /// the hash is followed by the `*` binary mode marker.
+    pub fn is_binary(&self) -> bool {
+        self.spans.separator == Some(Separator::Binary)
+    }
+
+    /// Whether the line was escaped by GNU Coreutils.
+    pub fn is_escaped(&self) -> bool {
+        self.spans.is_escaped()
+    }
+}
+
+/// Decodes a digest from base16 or base64 into bytes.
+///
+/// Returns `None` if the digest couldn't be decoded.
+pub(crate) fn decode_digest(digest: &str) -> Option<Vec<u8>> {
+    decode_hex(digest).or_else(|| {
+        base64::engine::general_purpose::STANDARD
+            .decode(digest)
+            .ok()
+    })
+}
+
+/// Decodes a base16 string into bytes.
+///
+/// Returns `None` if `digest` isn't made up of pairs of hexadecimal digits.
+fn decode_hex(digest: &str) -> Option<Vec<u8>> {
+    // `from_str_radix` would accept a leading `+`, which base64 digests use
+    if !digest
+        .bytes()
+        .all(|character| character.is_ascii_hexdigit())
+    {
+        return None;
+    }
+
+    (0..digest.len())
+        .step_by(2)
+        .map(|offset| u8::from_str_radix(digest.get(offset..offset + 2)?, 16).ok())
+        .collect()
+}
+
+/// Decodes the escape sequences GNU Coreutils uses
+/// in filenames (`\\`, `\n` and `\r`).
+///
+/// Unrecognised escape sequences are left unchanged.
+pub(crate) fn unescape(bytes: &[u8]) -> Vec<u8> {
+    let mut unescaped = Vec::with_capacity(bytes.len());
+    let mut remaining = bytes;
+
+    while let Some(offset) = remaining.iter().position(|&byte| byte == b'\\') {
+        unescaped.extend_from_slice(&remaining[..offset]);
+
+        let (decoded, length) = match remaining.get(offset + 1) {
+            Some(b'\\') => (b'\\', 2),
+            Some(b'n') => (b'\n', 2),
+            Some(b'r') => (b'\r', 2),
+            _ => (b'\\', 1),
+        };
+
+        unescaped.push(decoded);
+        remaining = &remaining[offset + length..];
+    }
+
+    unescaped.extend_from_slice(remaining);
+    unescaped
+}
+
+/// Finds the first offset of `needle` within `haystack`.
+fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// Finds the last offset of `needle` within `haystack`.
+fn rfind_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .rposition(|window| window == needle)
+}
+
+/// Algorithm names which appear in BSD "tag" style lines,
+/// and the length of their digests, in bytes.
+const BSD_TAG_ALGORITHMS: &[(&str, usize)] = &[
+    ("MD4", 16),
+    ("MD5", 16),
+    ("SHA1", 20),
+    ("SHA224", 28),
+    ("SHA256", 32),
+    ("SHA384", 48),
+    ("SHA512", 64),
+    ("SHA512/224", 28),
+    ("SHA512/256", 32),
+    ("SHA512t224", 28),
+    ("SHA512t256", 32),
+    ("SHA3-224", 28),
+    ("SHA3-256", 32),
+    ("SHA3-384", 48),
+    ("SHA3-512", 64),
+    ("BLAKE2b", 64),
+    ("BLAKE2s", 32),
+    ("BLAKE3", 32),
+    ("RMD160", 20),
+    ("RIPEMD160", 20),
+    ("SM3", 32),
+    ("SKEIN256", 32),
+    ("SKEIN512", 64),
+    ("SKEIN1024", 128),
+];
+
+/// Finds the length, in bytes, of digests produced by
+/// the algorithm named `algorithm` in a BSD "tag" style line.
+///
+/// As well as the names in `BSD_TAG_ALGORITHMS`, understands the
+/// `BLAKE2b-<bits>` names GNU `b2sum --length` uses.
+fn bsd_tag_digest_length(algorithm: &[u8]) -> Option<usize> {
+    if let Some((_, length)) = BSD_TAG_ALGORITHMS
+        .iter()
+        .find(|(name, _)| name.as_bytes().eq_ignore_ascii_case(algorithm))
+    {
+        return Some(*length);
+    }
+
+    let prefix = b"BLAKE2b-";
+    if algorithm.len() <= prefix.len() || !algorithm[..prefix.len()].eq_ignore_ascii_case(prefix) {
+        return None;
+    }
+
+    let bits: usize = std::str::from_utf8(&algorithm[prefix.len()..])
+        .ok()?
+        .parse()
+        .ok()?;
+
+    // the length must be a whole number of bytes, up to that of BLAKE2b-512
+    let length = bits / 8;
+    if length * 8 == bits && (1..=64).contains(&length) {
+        Some(length)
+    } else {
+        None
+    }
+}
+
+/// Detects the *starting* offset of the
+/// hash in a BSD `md5(1)` style line
+///
+/// The line must have the shape `ALGORITHM (path) = digest`, where
+/// `ALGORITHM` is a known algorithm name, and `digest` is the
+/// right length for that algorithm.
+fn find_bsd_tag_line(line: &[u8]) -> Option<usize> {
+    let algorithm_end = find_bytes(line, b" (")?;
+    let digest_length = bsd_tag_digest_length(&line[..algorithm_end])?;
+
+    let needle = b") = ";
+    let path_end = rfind_bytes(line, needle)?;
+
+    // the path may be empty, but must sit between the parentheses
+    if path_end < algorithm_end + 2 {
+        return None;
+    }
+
+    let suffix_start = path_end + needle.len();
+
+    if is_digest_of_length(&line[suffix_start..], digest_length) {
+        Some(suffix_start)
+    } else {
+        None
+    }
+}
+
+/// Digest lengths, in bytes, which checksum utilities commonly produce;
+/// from 32-bit CRCs and `xxhsum` output up to Skein-1024.
+const DIGEST_LENGTHS: &[usize] = &[4, 8, 16, 20, 28, 32, 48, 64, 128];
+
+/// The shortest digest length, in bytes, which may be followed by a single
+/// space; shorter digests are too easily confused with dates and numbers,
+/// such as `20261016 meeting notes`.
+const MIN_SPACE_SEPARATED_DIGEST_LENGTH: usize = 16;
+
+/// Determines whether `digest` looks like a base16 or base64
+/// encoded digest, of one of the `DIGEST_LENGTHS`.
+fn is_plausible_digest(digest: &[u8]) -> bool {
+    is_plausible_digest_of_at_least(digest, 0)
+}
+
+/// Determines whether `digest` looks like a base16 or base64 encoded
+/// digest, of one of the `DIGEST_LENGTHS` which is at least `minimum`.
+fn is_plausible_digest_of_at_least(digest: &[u8], minimum: usize) -> bool {
+    DIGEST_LENGTHS
+        .iter()
+        .filter(|&&length| length >= minimum)
+        .any(|&length| is_digest_of_length(digest, length))
+}
+
+/// Determines whether `digest` looks like a base16 or base64
+/// encoded digest which is `length` bytes long.
+fn is_digest_of_length(digest: &[u8], length: usize) -> bool {
+    if digest.iter().all(u8::is_ascii_hexdigit) {
+        return length * 2 == digest.len();
+    }
+
+    // base64 may only be padded at the very end, by up to two characters
+    let unpadded = digest
+        .strip_suffix(b"==")
+        .or_else(|| digest.strip_suffix(b"="))
+        .unwrap_or(digest);
+    let padding = digest.len() - unpadded.len();
+
+    // padded base64 uses four characters for every three bytes,
+    // padding the final group out to three bytes if necessary
+    let expected_padding = (3 - length % 3) % 3;
+
+    (length + expected_padding) / 3 * 4 == digest.len()
+        && expected_padding == padding
+        && unpadded
+            .iter()
+            .all(|&byte| byte.is_ascii_alphanumeric() || byte == b'+' || byte == b'/')
+}
+
+/// Detects the *ending* offset of the hash in a
+/// GNU `md5sum(1)` / perl `shasum(1)` style line,
+/// along with the separator which follows it.
+///
+/// The hash must be a plausible digest, followed by
+/// a separator and a non-empty filename. Only digests of at least
+/// `MIN_SPACE_SEPARATED_DIGEST_LENGTH` bytes may be followed by a single space.
+fn find_sum_prefixed_line(line: &[u8]) -> Option<(usize, Separator)> {
+    let prefix_end = line.iter().position(|&byte| byte == b' ')?;
+    let digest = &line[..prefix_end];
+
+    if !is_plausible_digest(digest) {
+        return None;
+    }
+
+    let separator = match line.get(prefix_end + 1)? {
+        b' ' => Separator::Text,
+        b'*' => Separator::Binary,
+        _ if is_plausible_digest_of_at_least(digest, MIN_SPACE_SEPARATED_DIGEST_LENGTH) => {
+            Separator::Space
+        }
+        _ => return None,
+    };
+
+    if line.len() <= prefix_end + separator.len() {
+        return None;
+    }
+
+    Some((prefix_end, separator))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn parse_works() {
+        use super::{ChecksumLine, LineStyle, Separator};
+
+        let line = ChecksumLine::parse(
+            b"3e08ba70bfc57da75612af458c7ea94108f9a9ddf9d1bfd96de9c0e34e684bda *./src/main.rs",
+        )
+        .unwrap();
+
+        assert_eq!(line.style(), LineStyle::Gnu);
+        assert_eq!(line.algorithm(), None);
+        assert_eq!(line.path(), b"./src/main.rs");
+        assert_eq!(
+            line.digest(),
+            "3e08ba70bfc57da75612af458c7ea94108f9a9ddf9d1bfd96de9c0e34e684bda"
+        );
+        assert_eq!(line.digest_range(), 0..64);
+        assert_eq!(line.digest_bytes().unwrap().len(), 32);
+        assert_eq!(line.separator(), Some(Separator::Binary));
+        assert!(line.is_binary());
+        assert!(!line.is_escaped());
+
+        let line = ChecksumLine::parse(
+            b"SHA256 (./src/main.rs) = uYhKSNXsM19ZCaD4PiMj7HtMSQ7pUu35PQugyT0aQeQ=",
+        )
+        .unwrap();
+
+        assert_eq!(line.style(), LineStyle::BsdTag);
+        assert_eq!(line.algorithm(), Some("SHA256"));
+        assert_eq!(line.path(), b"./src/main.rs");
+        assert_eq!(line.digest_range(), 25..69);
+        assert_eq!(line.digest_bytes().unwrap()[..4], [0xb9, 0x88, 0x4a, 0x48]);
+        assert_eq!(line.separator(), None);
+        assert!(!line.is_binary());
+
+        assert_eq!(ChecksumLine::parse(b"timeout = 30"), None);
+        assert_eq!(ChecksumLine::parse(b"hello  world"), None);
+    }
+
+    #[test]
+    fn parse_escaped_lines_works() {
+        use super::{ChecksumLine, LineStyle};
+
+        let line =
+            ChecksumLine::parse(b"\\b7527e0e28c09f6f62dd2d4197d5d225  ./a\\nb\\\\c").unwrap();
+
+        assert_eq!(line.style(), LineStyle::EscapedGnu);
+        assert_eq!(line.path(), b"./a\\nb\\\\c");
+        assert_eq!(&*line.unescaped_path(), b"./a\nb\\c");
+        assert_eq!(line.digest_range(), 1..33);
+        assert!(line.is_escaped());
+
+        let line =
+            ChecksumLine::parse(b"\\MD5 (./a\\nb) = b7527e0e28c09f6f62dd2d4197d5d225").unwrap();
+
+        assert_eq!(line.style(), LineStyle::EscapedBsdTag);
+        assert_eq!(line.algorithm(), Some("MD5"));
+        assert_eq!(line.path(), b"./a\\nb");
+        assert_eq!(&*line.unescaped_path(), b"./a\nb");
+        assert_eq!(line.digest_range(), 16..48);
+
+        assert_eq!(ChecksumLine::parse(b"\\not a checksum"), None);
+    }
+
+    #[test]
+    fn unescape_works() {
+        use super::unescape;
+
+        assert_eq!(unescape(b"./a\\nb\\\\c\\rd\\xe\\"), b"./a\nb\\c\rd\\xe\\");
+    }
+
+    #[test]
+    fn decode_hex_works() {
+        use super::decode_hex;
+
+        assert_eq!(decode_hex("b7527e"), Some(vec![0xb7, 0x52, 0x7e]));
+        assert_eq!(decode_hex("B7527E"), Some(vec![0xb7, 0x52, 0x7e]));
+        assert_eq!(decode_hex("b7527"), None);
+        assert_eq!(decode_hex("zz"), None);
+        assert_eq!(decode_hex("😄"), None);
+    }
+
+    #[test]
+    fn find_bsd_tag_line_works() {
+        use super::find_bsd_tag_line;
+
+        assert_eq!(
+            find_bsd_tag_line(b"MD5 (./src/main.rs) = b7527e0e28c09f6f62dd2d4197d5d225"),
+            Some(22)
+        );
+        assert_eq!(
+            find_bsd_tag_line(b"b7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs"),
+            None
+        );
+        assert_eq!(
+            find_bsd_tag_line(
+                b"3e08ba70bfc57da75612af458c7ea94108f9a9ddf9d1bfd96de9c0e34e684bda  ./src/main.rs"
+            ),
+            None
+        );
+        assert_eq!(
+            find_bsd_tag_line(
+                b"SHA256 (./src/main.rs) = 3e08ba70bfc57da75612af458c7ea94108f9a9ddf9d1bfd96de9c0e34e684bda"
+            ),
+            Some(25)
+        );
+        assert_eq!(
+            find_bsd_tag_line(
+                b"SHA256 (./src/main.rs) = uYhKSNXsM19ZCaD4PiMj7HtMSQ7pUu35PQugyT0aQeQ="
+            ),
+            Some(25)
+        );
+        assert_eq!(
+            find_bsd_tag_line(b"BLAKE2b-128 (./src/main.rs) = b7527e0e28c09f6f62dd2d4197d5d225"),
+            Some(30)
+        );
+        assert_eq!(
+            find_bsd_tag_line(b"MD5 (./a) = b) = b7527e0e28c09f6f62dd2d4197d5d225"),
+            Some(17)
+        );
+        assert_eq!(
+            find_bsd_tag_line(b"MD5 () = b7527e0e28c09f6f62dd2d4197d5d225"),
+            Some(9)
+        );
+    }
+
+    #[test]
+    fn find_bsd_tag_line_ignores_ordinary_text() {
+        use super::find_bsd_tag_line;
+
+        assert_eq!(find_bsd_tag_line(b"timeout = 30"), None);
+        assert_eq!(
+            find_bsd_tag_line(b"x = b7527e0e28c09f6f62dd2d4197d5d225"),
+            None
+        );
+        // unknown algorithm
+        assert_eq!(
+            find_bsd_tag_line(b"FOO (./src/main.rs) = b7527e0e28c09f6f62dd2d4197d5d225"),
+            None
+        );
+        // digest length doesn't match the algorithm
+        assert_eq!(
+            find_bsd_tag_line(b"SHA256 (./src/main.rs) = b7527e0e28c09f6f62dd2d4197d5d225"),
+            None
+        );
+        assert_eq!(find_bsd_tag_line(b"BLAKE2b-7 (./src/main.rs) = b7"), None);
+        // missing parentheses
+        assert_eq!(
+            find_bsd_tag_line(b"MD5 ./src/main.rs = b7527e0e28c09f6f62dd2d4197d5d225"),
+            None
+        );
+        assert_eq!(
+            find_bsd_tag_line(b"MD5 (./src/main.rs = b7527e0e28c09f6f62dd2d4197d5d225"),
+            None
+        );
+    }
+
+    #[test]
+    fn find_sum_prefixed_line_works() {
+        use super::find_sum_prefixed_line;
+        use super::Separator::{Binary, Space, Text};
+
+        assert_eq!(
+            find_sum_prefixed_line(b"b7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs"),
+            Some((32, Text))
+        );
+        assert_eq!(
+            find_sum_prefixed_line(
+                b"3e08ba70bfc57da75612af458c7ea94108f9a9ddf9d1bfd96de9c0e34e684bda  ./src/main.rs"
+            ),
+            Some((64, Text))
+        );
+        assert_eq!(
+            find_sum_prefixed_line(b"b7527e0e28c09f6f62dd2d4197d5d225 *./src/main.rs"),
+            Some((32, Binary))
+        );
+        assert_eq!(
+            find_sum_prefixed_line(b"b7527e0e28c09f6f62dd2d4197d5d225 ./src/main.rs"),
+            Some((32, Space))
+        );
+        // 1024-bit digests, such as from `skein1024sum`
+        assert_eq!(
+            find_sum_prefixed_line(format!("{}  ./src/main.rs", "3e08ba70".repeat(32)).as_bytes()),
+            Some((256, Text))
+        );
+        assert_eq!(
+            find_sum_prefixed_line(b"8a5ea8a9  ./src/main.rs"),
+            Some((8, Text))
+        );
+        assert_eq!(
+            find_sum_prefixed_line(b"8a5ea8a9 *./src/main.rs"),
+            Some((8, Binary))
+        );
+        assert_eq!(
+            find_sum_prefixed_line(b"t1J+DijAn2Ji3UGXXF5zaA==  ./src/main.rs"),
+            Some((24, Text))
+        );
+        assert_eq!(
+            find_sum_prefixed_line(b"MD5 (./src/main.rs) = b7527e0e28c09f6f62dd2d4197d5d225"),
+            None
+        );
+    }
+
+    #[test]
+    fn find_sum_prefixed_line_ignores_ordinary_text() {
+        use super::find_sum_prefixed_line;
+
+        assert_eq!(find_sum_prefixed_line(b"hello  world"), None);
+        assert_eq!(find_sum_prefixed_line(b"hello world"), None);
+        assert_eq!(find_sum_prefixed_line(b"  indented text"), None);
+        assert_eq!(find_sum_prefixed_line(b"cafe  babe"), None);
+        // short digests need a mode marker or two spaces
+        assert_eq!(find_sum_prefixed_line(b"20261016 meeting notes"), None);
+        assert_eq!(find_sum_prefixed_line(b"12345678 items shipped"), None);
+        assert_eq!(find_sum_prefixed_line(b"deadbeef cafe"), None);
+        assert_eq!(
+            find_sum_prefixed_line(b"b7527e0e28c09f6f ./src/main.rs"),
+            None
+        );
+        // odd number of hex digits
+        assert_eq!(
+            find_sum_prefixed_line(b"b7527e0e28c09f6f62dd2d4197d5d22  ./src/main.rs"),
+            None
+        );
+        // no filename
+        assert_eq!(
+            find_sum_prefixed_line(b"b7527e0e28c09f6f62dd2d4197d5d225  "),
+            None
+        );
+        // misplaced base64 padding
+        assert_eq!(
+            find_sum_prefixed_line(b"t1J+DijAn2Ji3UGX=F5zaA==  ./src/main.rs"),
+            None
+        );
+    }
+
+    #[test]
+    fn is_plausible_digest_works() {
+        use super::is_plausible_digest;
+
+        assert!(is_plausible_digest(b"b7527e0e28c09f6f62dd2d4197d5d225"));
+        assert!(is_plausible_digest(b"B7527E0E28C09F6F62DD2D4197D5D225"));
+        assert!(is_plausible_digest(b"8a5ea8a9"));
+        assert!(is_plausible_digest(
+            b"uYhKSNXsM19ZCaD4PiMj7HtMSQ7pUu35PQugyT0aQeQ="
+        ));
+
+        assert!(!is_plausible_digest(b""));
+        assert!(!is_plausible_digest(b"b7527e"));
+        assert!(!is_plausible_digest(b"b7527e0e28c09f6f62dd2d4197d5d2"));
+        assert!(!is_plausible_digest(b"hello"));
+        assert!(!is_plausible_digest(
+            b"uYhKSNXsM19ZCaD4PiMj7HtMSQ7pUu35PQugyT0aQeQ"
+        ));
+    }
+}
@@ -0,0 +1,625 @@
+//! Colour space conversions, and terminal colour output,
+//! shared by the colouring formatters.
+
+use std::fmt;
+
+use crate::colour_support::ColourSupport;
+
+/// The golden angle, in degrees.
+///
+/// Stepping around the hue circle by this angle never lands near a hue
+/// which has already been used, so consecutive values get distant hues.
+pub(crate) const GOLDEN_ANGLE: f32 = 137.507_77;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// A colour in the OKLCh colour space; the polar form of OKLab.
+///
+/// Equal steps in OKLCh look like roughly equal changes in colour,
+/// unlike steps in RGB, or in the xterm 256 colour palette.
+pub(crate) struct Oklch {
+    /// Perceived lightness, from `0.0` (black) to `1.0` (white).
+    pub lightness: f32,
+    /// Colourfulness, from `0.0` (grey) to around `0.37`.
+    pub chroma: f32,
+    /// Hue angle, in degrees.
+    pub hue: f32,
+}
+
+impl Oklch {
+    /// Converts the colour to OKLab's `(L, a, b)` coordinates.
+    pub fn to_oklab(self) -> (f32, f32, f32) {
+        let (sin, cos) = self.hue.to_radians().sin_cos();
+
+        (self.lightness, self.chroma * cos, self.chroma * sin)
+    }
+
+    /// Converts the colour to 8-bit sRGB.
+    ///
+    /// Colours which sRGB can't represent have their chroma reduced,
+    /// keeping their lightness and hue, until they fit.
+    pub fn to_rgb(self) -> (u8, u8, u8) {
+        if let Some(rgb) = self.to_linear_rgb_in_gamut() {
+            return encode_srgb(rgb);
+        }
+
+        // binary search for the most colourful version which fits;
+        // a grey of any lightness always fits, so there always is one
+        let (mut fits, mut doesnt_fit) = (0.0, self.chroma);
+        for _ in 0..16 {
+            let chroma = (fits + doesnt_fit) / 2.0;
+
+            match (Oklch { chroma, ..self }).to_linear_rgb_in_gamut() {
+                Some(_) => fits = chroma,
+                None => doesnt_fit = chroma,
+            }
+        }
+
+        let (red, green, blue) = Oklch {
+            chroma: fits,
+            ..self
+        }
+        .to_linear_rgb();
+
+        encode_srgb((
+            red.clamp(0.0, 1.0),
+            green.clamp(0.0, 1.0),
+            blue.clamp(0.0, 1.0),
+        ))
+    }
+
+    /// Converts the colour to linear sRGB, if it is within sRGB's gamut.
+    fn to_linear_rgb_in_gamut(self) -> Option<(f32, f32, f32)> {
+        // allow a little slack for rounding errors
+        let in_gamut = |channel: f32| (-0.001..=1.001).contains(&channel);
+
+        match self.to_linear_rgb() {
+            (red, green, blue) if in_gamut(red) && in_gamut(green) && in_gamut(blue) => Some((
+                red.clamp(0.0, 1.0),
+                green.clamp(0.0, 1.0),
+                blue.clamp(0.0, 1.0),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Converts the colour to linear sRGB, which may be out of gamut.
+    ///
+    /// See <https://bottosson.github.io/posts/oklab/> for the matrices.
+    fn to_linear_rgb(self) -> (f32, f32, f32) {
+        let (lightness, a, b) = self.to_oklab();
+
+        let l = (lightness + 0.396_337_78 * a + 0.215_803_76 * b).powi(3);
+        let m = (lightness - 0.105_561_346 * a - 0.063_854_17 * b).powi(3);
+        let s = (lightness - 0.089_484_18 * a - 1.291_485_5 * b).powi(3);
+
+        (
+            4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s,
+            -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s,
+            -0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s,
+        )
+    }
+}
+
+/// Applies the sRGB transfer function to a linear colour,
+/// and quantises it to 8 bits per channel.
+fn encode_srgb((red, green, blue): (f32, f32, f32)) -> (u8, u8, u8) {
+    fn encode(channel: f32) -> u8 {
+        let encoded = if channel <= 0.003_130_8 {
+            channel * 12.92
+        } else {
+            1.055 * channel.powf(1.0 / 2.4) - 0.055
+        };
+
+        (encoded * 255.0).round() as u8
+    }
+
+    (encode(red), encode(green), encode(blue))
+}
+
+/// The levels of each channel in the xterm 256 colour palette's colour cube.
+const XTERM_CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Finds the closest colour to `rgb` in the xterm 256 colour palette,
+/// returning its index, and its actual colour.
+///
+/// Only the colour cube and greyscale ramp (colours 16 to 255) are
+/// considered, as colours 0 to 15 vary between terminal themes.
+pub(crate) fn xterm_colour((red, green, blue): (u8, u8, u8)) -> (u8, (u8, u8, u8)) {
+    fn closest_level(channel: u8) -> usize {
+        (0..XTERM_CUBE_LEVELS.len())
+            .min_by_key(|&level| XTERM_CUBE_LEVELS[level].abs_diff(channel))
+            .unwrap_or_default()
+    }
+
+    let (cube_red, cube_green, cube_blue) = (
+        closest_level(red),
+        closest_level(green),
+        closest_level(blue),
+    );
+    let cube_colour = (
+        XTERM_CUBE_LEVELS[cube_red],
+        XTERM_CUBE_LEVELS[cube_green],
+        XTERM_CUBE_LEVELS[cube_blue],
+    );
+    // the cube has an index for each channel, counting in base 6
+    let cube_index = 16 + 36 * cube_red + 6 * cube_green + cube_blue;
+
+    // the greyscale ramp runs from 8 to 238, in steps of 10
+    let average = (u16::from(red) + u16::from(green) + u16::from(blue)) / 3;
+    let grey_step = (average.saturating_sub(3) / 10).min(23) as u8;
+    let grey = 8 + 10 * grey_step;
+    let grey_colour = (grey, grey, grey);
+
+    let rgb = (red, green, blue);
+    if distance(rgb, grey_colour) < distance(rgb, cube_colour) {
+        (232 + grey_step, grey_colour)
+    } else {
+        (cube_index as u8, cube_colour)
+    }
+}
+
+/// The colours xterm uses by default for the 16 basic ANSI colours.
+///
+/// Terminal themes often change these, so they're only a rough guide.
+const BASIC_COLOURS: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// Finds the closest of the 16 basic ANSI colours to `rgb`.
+pub(crate) fn basic_colour(rgb: (u8, u8, u8)) -> u8 {
+    (0..BASIC_COLOURS.len())
+        .min_by_key(|&index| distance(rgb, BASIC_COLOURS[index]))
+        .unwrap_or_default() as u8
+}
+
+/// Replaces `index` in the xterm 256 colour palette, if it's one of the
+/// 16 basic colours which vary between terminal themes, with the closest
+/// colour in the colour cube or greyscale ramp, whose colour is known.
+pub(crate) fn theme_independent_colour(index: u8) -> u8 {
+    match index {
+        0..=15 => xterm_colour(xterm_rgb(index)).0,
+        _ => index,
+    }
+}
+
+/// The colour of `index` in the xterm 256 colour palette.
+fn xterm_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => BASIC_COLOURS[usize::from(index)],
+        16..=231 => {
+            let cube = usize::from(index - 16);
+
+            (
+                XTERM_CUBE_LEVELS[cube / 36],
+                XTERM_CUBE_LEVELS[cube / 6 % 6],
+                XTERM_CUBE_LEVELS[cube % 6],
+            )
+        }
+        _ => {
+            let grey = 8 + 10 * (index - 232);
+
+            (grey, grey, grey)
+        }
+    }
+}
+
+/// The squared Euclidean distance between two colours.
+pub(crate) fn distance(first: (u8, u8, u8), second: (u8, u8, u8)) -> u32 {
+    let square = |first: u8, second: u8| u32::from(first.abs_diff(second)).pow(2);
+
+    square(first.0, second.0) + square(first.1, second.1) + square(first.2, second.2)
+}
+
+/// Writes `value` as a decimal number into `buffer`, returning the digits.
+fn decimal(value: u8, buffer: &mut [u8; 3]) -> &str {
+    buffer[0] = b'0' + value / 100;
+    buffer[1] = b'0' + value / 10 % 10;
+    buffer[2] = b'0' + value % 10;
+
+    let leading_zeroes = match value {
+        0..=9 => 2,
+        10..=99 => 1,
+        _ => 0,
+    };
+
+    // these are all ASCII digits, so this can't fail
+    std::str::from_utf8(&buffer[leading_zeroes..]).unwrap_or_default()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// A colour which a formatter would like to paint text with.
+pub(crate) enum TermColour {
+    /// An index into the xterm 256 colour palette.
+    Fixed(u8),
+    /// A 24-bit colour.
+    Rgb(u8, u8, u8),
+}
+
+impl TermColour {
+    /// The colour's approximate 24-bit value.
+    fn rgb(self) -> (u8, u8, u8) {
+        match self {
+            TermColour::Fixed(index) => xterm_rgb(index),
+            TermColour::Rgb(red, green, blue) => (red, green, blue),
+        }
+    }
+
+    /// Writes the SGR parameters which select this colour as the foreground
+    /// or background colour, downgraded to the best colour `support` allows.
+    ///
+    /// Writes nothing if `support` is `ColourSupport::None`.
+    fn write_parameters(
+        self,
+        to: &mut dyn fmt::Write,
+        support: ColourSupport,
+        layer: Layer,
+    ) -> fmt::Result {
+        // the codes for foreground colours, and the bright basic colours,
+        // all have their background equivalent 10 higher
+        let (extended, basic, bright) = match layer {
+            Layer::Foreground => ("38", 30, 90),
+            Layer::Background => ("48", 40, 100),
+        };
+
+        match (self, support) {
+            (_, ColourSupport::None) => Ok(()),
+            (TermColour::Rgb(red, green, blue), ColourSupport::Truecolour) => {
+                to.write_str(extended)?;
+                to.write_str(";2;")?;
+                to.write_str(decimal(red, &mut [0; 3]))?;
+                to.write_char(';')?;
+                to.write_str(decimal(green, &mut [0; 3]))?;
+                to.write_char(';')?;
+                to.write_str(decimal(blue, &mut [0; 3]))
+            }
+            (TermColour::Rgb(red, green, blue), ColourSupport::Extended) => {
+                TermColour::Fixed(xterm_colour((red, green, blue)).0)
+                    .write_parameters(to, support, layer)
+            }
+            (TermColour::Fixed(index), ColourSupport::Extended | ColourSupport::Truecolour) => {
+                to.write_str(extended)?;
+                to.write_str(";5;")?;
+                to.write_str(decimal(index, &mut [0; 3]))
+            }
+            (colour, ColourSupport::Basic) => {
+                let index = match colour {
+                    TermColour::Fixed(index @ 0..=15) => index,
+                    colour => basic_colour(colour.rgb()),
+                };
+
+                // the bright colours have their own range of codes
+                match index {
+                    0..=7 => to.write_str(decimal(basic + index, &mut [0; 3])),
+                    _ => to.write_str(decimal(bright + index - 8, &mut [0; 3])),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Which part of the text a colour applies to.
+enum Layer {
+    Foreground,
+    Background,
+}
+
+/// Writes `text` to `to`, painted in `colour`, downgraded
+/// to the best colour `support` allows.
+///
+/// If `support` is `ColourSupport::None`, `text` is written as it is.
+pub(crate) fn write_painted(
+    to: &mut dyn fmt::Write,
+    colour: TermColour,
+    text: &str,
+    support: ColourSupport,
+) -> fmt::Result {
+    if support == ColourSupport::None {
+        return to.write_str(text);
+    }
+
+    // equivalent to `ansi_term`'s `Colour::paint`, but
+    // without the overhead of `fmt` for every single byte
+    to.write_str("\x1b[")?;
+    colour.write_parameters(to, support, Layer::Foreground)?;
+    to.write_char('m')?;
+    to.write_str(text)?;
+    to.write_str("\x1b[0m")
+}
+
+/// Writes `text` to `to`, on a block of `background` colour, downgraded
+/// to the best colour `support` allows.
+///
+/// The text itself is painted black or white, whichever
+/// contrasts best with the background.
+///
+/// If `support` is `ColourSupport::None`, `text` is written as it is.
+pub(crate) fn write_block(
+    to: &mut dyn fmt::Write,
+    background: TermColour,
+    text: &str,
+    support: ColourSupport,
+) -> fmt::Result {
+    if support == ColourSupport::None {
+        return to.write_str(text);
+    }
+
+    // the cube's black and white are the same on every theme, unlike the
+    // basic colours, which only need to be used if nothing else is supported
+    let foreground = if relative_luminance(background.rgb()) > CONTRAST_THRESHOLD {
+        TermColour::Fixed(16)
+    } else {
+        TermColour::Fixed(231)
+    };
+
+    to.write_str("\x1b[")?;
+    background.write_parameters(to, support, Layer::Background)?;
+    to.write_char(';')?;
+    foreground.write_parameters(to, support, Layer::Foreground)?;
+    to.write_char('m')?;
+    to.write_str(text)?;
+    to.write_str("\x1b[0m")
+}
+
+/// Writes a single upper half block (`▀`) to `to`, with its top half
+/// painted in `upper`, and its bottom half in `lower`, downgraded
+/// to the best colours `support` allows.
+///
+/// If `lower` is `None`, the bottom half is left blank. If `support`
+/// is `ColourSupport::None`, nothing is written, as the block
+/// would be meaningless without its colours.
+pub(crate) fn write_half_block(
+    to: &mut dyn fmt::Write,
+    upper: TermColour,
+    lower: Option<TermColour>,
+    support: ColourSupport,
+) -> fmt::Result {
+    if support == ColourSupport::None {
+        return Ok(());
+    }
+
+    to.write_str("\x1b[")?;
+    upper.write_parameters(to, support, Layer::Foreground)?;
+    if let Some(lower) = lower {
+        to.write_char(';')?;
+        lower.write_parameters(to, support, Layer::Background)?;
+    }
+    to.write_str("m▀\x1b[0m")
+}
+
+/// The relative luminance above which black text contrasts better than
+/// white text; that is, where `(L + 0.05) / 0.05 == 1.05 / (L + 0.05)`.
+const CONTRAST_THRESHOLD: f32 = 0.179;
+
+/// The relative luminance of a colour, as defined by WCAG 2;
+/// from `0.0` for black to `1.0` for white.
+pub(crate) fn relative_luminance((red, green, blue): (u8, u8, u8)) -> f32 {
+    fn decode(channel: u8) -> f32 {
+        let channel = f32::from(channel) / 255.0;
+
+        if channel <= 0.040_45 {
+            channel / 12.92
+        } else {
+            ((channel + 0.055) / 1.055).powf(2.4)
+        }
+    }
+
+    0.212_6 * decode(red) + 0.715_2 * decode(green) + 0.072_2 * decode(blue)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn to_rgb_works() {
+        use super::Oklch;
+
+        let white = Oklch {
+            lightness: 1.0,
+            chroma: 0.0,
+            hue: 0.0,
+        };
+        let black = Oklch {
+            lightness: 0.0,
+            chroma: 0.0,
+            hue: 0.0,
+        };
+        // sRGB red is approximately oklch(0.628 0.2577 29.23)
+        let red = Oklch {
+            lightness: 0.628,
+            chroma: 0.2577,
+            hue: 29.23,
+        };
+
+        assert_eq!(white.to_rgb(), (255, 255, 255));
+        assert_eq!(black.to_rgb(), (0, 0, 0));
+        assert_eq!(red.to_rgb(), (255, 0, 0));
+    }
+
+    #[test]
+    fn xterm_colour_works() {
+        use super::xterm_colour;
+
+        assert_eq!(xterm_colour((0, 0, 0)), (16, (0, 0, 0)));
+        assert_eq!(xterm_colour((255, 255, 255)), (231, (255, 255, 255)));
+        assert_eq!(xterm_colour((255, 0, 0)), (196, (255, 0, 0)));
+        assert_eq!(xterm_colour((100, 140, 210)), (68, (95, 135, 215)));
+        assert_eq!(xterm_colour((128, 128, 128)), (244, (128, 128, 128)));
+        assert_eq!(xterm_colour((240, 240, 240)), (255, (238, 238, 238)));
+    }
+
+    #[test]
+    fn xterm_rgb_works() {
+        use super::{xterm_colour, xterm_rgb};
+
+        assert_eq!(xterm_rgb(1), (205, 0, 0));
+        assert_eq!(xterm_rgb(68), (95, 135, 215));
+        assert_eq!(xterm_rgb(244), (128, 128, 128));
+
+        for index in 16..=255 {
+            assert_eq!(xterm_colour(xterm_rgb(index)).1, xterm_rgb(index));
+        }
+    }
+
+    #[test]
+    fn theme_independent_colour_works() {
+        use super::theme_independent_colour;
+
+        assert_eq!(theme_independent_colour(0), 16);
+        assert_eq!(theme_independent_colour(1), 160);
+        assert_eq!(theme_independent_colour(8), 244);
+        assert_eq!(theme_independent_colour(15), 231);
+        assert_eq!(theme_independent_colour(68), 68);
+        assert_eq!(theme_independent_colour(244), 244);
+
+        for index in 0..=255 {
+            assert!(theme_independent_colour(index) >= 16);
+        }
+    }
+
+    #[test]
+    fn decimal_works() {
+        use super::decimal;
+
+        assert_eq!(decimal(0, &mut [0; 3]), "0");
+        assert_eq!(decimal(7, &mut [0; 3]), "7");
+        assert_eq!(decimal(40, &mut [0; 3]), "40");
+        assert_eq!(decimal(100, &mut [0; 3]), "100");
+        assert_eq!(decimal(255, &mut [0; 3]), "255");
+    }
+
+    #[test]
+    fn write_painted_downgrades_colours() {
+        use super::{write_painted, TermColour};
+        use crate::ColourSupport;
+
+        let paint = |colour, support| {
+            let mut painted = String::new();
+            write_painted(&mut painted, colour, "4a", support).unwrap();
+            painted
+        };
+
+        let orange = TermColour::Rgb(255, 135, 0);
+        assert_eq!(
+            paint(orange, ColourSupport::Truecolour),
+            "\u{1b}[38;2;255;135;0m4a\u{1b}[0m"
+        );
+        assert_eq!(
+            paint(orange, ColourSupport::Extended),
+            "\u{1b}[38;5;208m4a\u{1b}[0m"
+        );
+        assert_eq!(paint(orange, ColourSupport::Basic), "\u{1b}[33m4a\u{1b}[0m");
+        assert_eq!(paint(orange, ColourSupport::None), "4a");
+
+        let blue = TermColour::Fixed(4);
+        assert_eq!(
+            paint(blue, ColourSupport::Truecolour),
+            "\u{1b}[38;5;4m4a\u{1b}[0m"
+        );
+        assert_eq!(paint(blue, ColourSupport::Basic), "\u{1b}[34m4a\u{1b}[0m");
+        assert_eq!(
+            paint(TermColour::Fixed(226), ColourSupport::Basic),
+            "\u{1b}[93m4a\u{1b}[0m"
+        );
+    }
+
+    #[test]
+    fn write_block_picks_contrasting_text() {
+        use super::{write_block, TermColour};
+        use crate::ColourSupport;
+
+        let block = |colour, support| {
+            let mut block = String::new();
+            write_block(&mut block, colour, "4a", support).unwrap();
+            block
+        };
+
+        assert_eq!(
+            block(TermColour::Fixed(226), ColourSupport::Extended),
+            "\u{1b}[48;5;226;38;5;16m4a\u{1b}[0m"
+        );
+        assert_eq!(
+            block(TermColour::Fixed(18), ColourSupport::Extended),
+            "\u{1b}[48;5;18;38;5;231m4a\u{1b}[0m"
+        );
+        assert_eq!(
+            block(TermColour::Rgb(0, 0, 135), ColourSupport::Truecolour),
+            "\u{1b}[48;2;0;0;135;38;5;231m4a\u{1b}[0m"
+        );
+        assert_eq!(
+            block(TermColour::Fixed(226), ColourSupport::Basic),
+            "\u{1b}[103;30m4a\u{1b}[0m"
+        );
+        assert_eq!(block(TermColour::Fixed(226), ColourSupport::None), "4a");
+    }
+
+    #[test]
+    fn write_half_block_works() {
+        use super::{write_half_block, TermColour};
+        use crate::ColourSupport;
+
+        let half_block = |lower, support| {
+            let mut half_block = String::new();
+            write_half_block(&mut half_block, TermColour::Fixed(183), lower, support).unwrap();
+            half_block
+        };
+
+        assert_eq!(
+            half_block(Some(TermColour::Fixed(82)), ColourSupport::Extended),
+            "\u{1b}[38;5;183;48;5;82m▀\u{1b}[0m"
+        );
+        assert_eq!(
+            half_block(None, ColourSupport::Extended),
+            "\u{1b}[38;5;183m▀\u{1b}[0m"
+        );
+        assert_eq!(
+            half_block(Some(TermColour::Fixed(82)), ColourSupport::None),
+            ""
+        );
+    }
+
+    #[test]
+    fn relative_luminance_works() {
+        use super::relative_luminance;
+
+        assert_eq!(relative_luminance((0, 0, 0)), 0.0);
+        assert!((relative_luminance((255, 255, 255)) - 1.0).abs() < 0.001);
+        assert!((relative_luminance((255, 0, 0)) - 0.2126).abs() < 0.001);
+    }
+
+    #[test]
+    fn to_rgb_reduces_chroma_of_out_of_gamut_colours() {
+        use super::Oklch;
+
+        // far more colourful than sRGB can represent
+        let colour = Oklch {
+            lightness: 0.7,
+            chroma: 0.4,
+            hue: 250.0,
+        };
+        let grey = Oklch {
+            chroma: 0.0,
+            ..colour
+        };
+
+        let (red, green, blue) = colour.to_rgb();
+
+        // still blue, rather than clipped to some other hue
+        assert!(blue > green && green > red);
+        assert_ne!(colour.to_rgb(), grey.to_rgb());
+    }
+}
@@ -0,0 +1,225 @@
+use std::convert::From;
+use std::fmt;
+use std::fmt::Display;
+
+use crate::ansi_coloured_line::{byte_colour, ANSIColouredLine};
+use crate::base_line::{format_to_string, FormattableLine, Line};
+use crate::checksum_line::decode_digest;
+use crate::colour::{write_half_block, TermColour};
+use crate::colour_support::ColourSupport;
+use crate::format_options::FormatOptions;
+
+#[derive(Debug)]
+/// Line with the digest replaced by a bar of half-block characters,
+/// each showing the xterm colours of two bytes, one above the other.
+pub struct ColourBarLine(FormattableLine);
+impl From<String> for ColourBarLine {
+    fn from(contents: String) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl From<Vec<u8>> for ColourBarLine {
+    fn from(contents: Vec<u8>) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl Display for ColourBarLine {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.to_formatted(formatter)
+    }
+}
+
+impl Line for ColourBarLine {
+    fn get_line(&self) -> &FormattableLine {
+        &self.0
+    }
+
+    fn format_hash(hash: String) -> String {
+        format_to_string(&hash, Self::write_hash)
+    }
+
+    /// Formats a base16 or base64-format hash or digest.
+    ///
+    /// Each pair of bytes will be drawn as one half block, coloured
+    /// with the same xterm colours as `ANSIColouredLine` uses, so
+    /// a SHA-256 digest fits in 16 columns.
+    fn write_hash(hash: &str, to: &mut dyn fmt::Write) -> fmt::Result {
+        Self::write_hash_with_options(hash, to, &FormatOptions::default())
+    }
+
+    /// Formats a base16 or base64-format hash or digest.
+    ///
+    /// Each pair of bytes will be drawn as one half block, coloured as
+    /// `ANSIColouredLine` colours them with `options`. Without any colours,
+    /// the bar would be meaningless, so the digest is written unchanged.
+    fn write_hash_with_options(
+        hash: &str,
+        to: &mut dyn fmt::Write,
+        options: &FormatOptions,
+    ) -> fmt::Result {
+        match colour_bar_bytes(hash, options) {
+            Some(bytes) => write_colour_bar(&bytes, to, options),
+            // if there was an error at any point, write the original value
+            None => to.write_str(hash),
+        }
+    }
+}
+
+#[derive(Debug)]
+/// Line with `ANSIColouredLine`'s formatting, followed by
+/// the same bar of half-block characters `ColourBarLine` draws.
+pub struct HexColourBarLine(FormattableLine);
+impl From<String> for HexColourBarLine {
+    fn from(contents: String) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl From<Vec<u8>> for HexColourBarLine {
+    fn from(contents: Vec<u8>) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl Display for HexColourBarLine {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.to_formatted(formatter)
+    }
+}
+
+impl Line for HexColourBarLine {
+    fn get_line(&self) -> &FormattableLine {
+        &self.0
+    }
+
+    fn format_hash(hash: String) -> String {
+        format_to_string(&hash, Self::write_hash)
+    }
+
+    /// Formats a base16-format hash or digest.
+    ///
+    /// The digest will be coloured as `ANSIColouredLine` colours it,
+    /// and followed by a space and its colour bar.
+    fn write_hash(hash: &str, to: &mut dyn fmt::Write) -> fmt::Result {
+        Self::write_hash_with_options(hash, to, &FormatOptions::default())
+    }
+
+    /// Formats a base16-format hash or digest.
+    ///
+    /// The digest will be coloured as `ANSIColouredLine` colours it with
+    /// `options`, and followed by a space and its colour bar, if there
+    /// are any colours to draw it with.
+    fn write_hash_with_options(
+        hash: &str,
+        to: &mut dyn fmt::Write,
+        options: &FormatOptions,
+    ) -> fmt::Result {
+        ANSIColouredLine::write_hash_with_options(hash, to, options)?;
+
+        match colour_bar_bytes(hash, options) {
+            Some(bytes) => {
+                to.write_char(' ')?;
+                write_colour_bar(&bytes, to, options)
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// Decodes `hash`, from base16 or base64, into the bytes
+/// to draw in its colour bar.
+///
+/// Returns `None` if `hash` can't be decoded, or if there
+/// are no colours to draw the bar with.
+fn colour_bar_bytes(hash: &str, options: &FormatOptions) -> Option<Vec<u8>> {
+    if options.colour_support == ColourSupport::None {
+        return None;
+    }
+
+    decode_digest(hash)
+}
+
+/// Writes the colour bar for `bytes` to `to`; each half block shows
+/// one byte's colour above the next's.
+fn write_colour_bar(bytes: &[u8], to: &mut dyn fmt::Write, options: &FormatOptions) -> fmt::Result {
+    let colour = |byte: u8| TermColour::Fixed(byte_colour(byte, options.palette));
+
+    for pair in bytes.chunks(2) {
+        write_half_block(
+            to,
+            colour(pair[0]),
+            pair.get(1).map(|&byte| colour(byte)),
+            options.colour_support,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn display_works() {
+        use super::ColourBarLine;
+
+        assert_eq!(
+            format!(
+                "{}",
+                ColourBarLine::from("b7527e0e  ./src/main.rs".to_string())
+            ),
+            "\u{1b}[38;5;183;48;5;82m▀\u{1b}[0m\u{1b}[38;5;126;48;5;14m▀\u{1b}[0m  ./src/main.rs"
+        )
+    }
+
+    #[test]
+    fn format_hash_works() {
+        use super::{ColourBarLine, HexColourBarLine};
+        use crate::{ANSIColouredLine, Line};
+
+        let hash = "b7527e0e28c09f6f62dd2d4197d5d225".to_string();
+        let bar = ColourBarLine::format_hash(hash.clone());
+
+        // a 16 byte digest fits in 8 columns
+        assert_eq!(bar.matches('▀').count(), 8);
+        assert_eq!(
+            HexColourBarLine::format_hash(hash.clone()),
+            format!("{} {}", ANSIColouredLine::format_hash(hash), bar)
+        );
+
+        // odd bytes leave the bottom half of the last block blank
+        assert_eq!(
+            ColourBarLine::format_hash("b7527e".to_string()),
+            "\u{1b}[38;5;183;48;5;82m▀\u{1b}[0m\u{1b}[38;5;126m▀\u{1b}[0m"
+        );
+    }
+
+    #[test]
+    fn write_hash_with_options_without_colours() {
+        use super::{ColourBarLine, HexColourBarLine};
+        use crate::{ColourSupport, FormatOptions, Line};
+
+        let options = FormatOptions {
+            colour_support: ColourSupport::None,
+            ..FormatOptions::default()
+        };
+        let mut bar = String::new();
+        ColourBarLine::write_hash_with_options("b7527e0e", &mut bar, &options).unwrap();
+        assert_eq!(bar, "b7527e0e");
+
+        let mut hex_and_bar = String::new();
+        HexColourBarLine::write_hash_with_options("b7527e0e", &mut hex_and_bar, &options).unwrap();
+        assert_eq!(hex_and_bar, "b7527e0e");
+    }
+
+    #[test]
+    fn format_hash_doesnt_crash_on_non_base16_characters() {
+        use super::{ColourBarLine, HexColourBarLine};
+        use crate::Line;
+
+        assert_eq!(ColourBarLine::format_hash("ASD!".to_string()), "ASD!");
+        assert_eq!(ColourBarLine::format_hash("😄".to_string()), "😄");
+        assert_eq!(HexColourBarLine::format_hash("ASD!".to_string()), "ASD!");
+    }
+}
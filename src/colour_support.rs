@@ -0,0 +1,92 @@
+use std::env;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+/// Which colours a terminal can display.
+///
+/// Colouring formatters downgrade the colours they'd like to use to the
+/// closest ones the terminal supports. The default, `Truecolour`, leaves
+/// every formatter's colours as they are.
+pub enum ColourSupport {
+    /// No colours at all; digests are written uncoloured.
+    None,
+    /// The 8 basic ANSI colours, and their 8 bright variants.
+    Basic,
+    /// The xterm 256 colour palette.
+    Extended,
+    /// 24-bit colour.
+    #[default]
+    Truecolour,
+}
+
+impl ColourSupport {
+    /// Detects which colours the current terminal supports, from the
+    /// `COLORTERM` and `TERM` environment variables.
+    ///
+    /// This doesn't check whether output is going to a terminal at all;
+    /// that's up to the caller.
+    pub fn detect() -> Self {
+        let colorterm = env::var("COLORTERM").ok();
+        let term = env::var("TERM").ok();
+
+        Self::from_terminal(colorterm.as_deref(), term.as_deref())
+    }
+
+    /// Works out which colours a terminal supports, from the values
+    /// of its `COLORTERM` and `TERM` environment variables.
+    pub fn from_terminal(colorterm: Option<&str>, term: Option<&str>) -> Self {
+        match term {
+            // terminals which can't display colours at all
+            // can't display 24-bit colours either
+            Some("dumb") => ColourSupport::None,
+            _ if matches!(colorterm, Some("truecolor" | "24bit")) => ColourSupport::Truecolour,
+            Some(term) if term.ends_with("-direct") => ColourSupport::Truecolour,
+            Some(term) if term.contains("256color") => ColourSupport::Extended,
+            Some(_) => ColourSupport::Basic,
+            // Windows terminals don't set `TERM`, but Windows Terminal
+            // and recent versions of the Windows console support 24-bit colour
+            None if cfg!(windows) => ColourSupport::Truecolour,
+            None => ColourSupport::Basic,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn from_terminal_works() {
+        use super::ColourSupport;
+
+        assert_eq!(
+            ColourSupport::from_terminal(Some("truecolor"), Some("xterm-256color")),
+            ColourSupport::Truecolour
+        );
+        assert_eq!(
+            ColourSupport::from_terminal(Some("24bit"), Some("screen")),
+            ColourSupport::Truecolour
+        );
+        assert_eq!(
+            ColourSupport::from_terminal(None, Some("xterm-direct")),
+            ColourSupport::Truecolour
+        );
+        assert_eq!(
+            ColourSupport::from_terminal(None, Some("xterm-256color")),
+            ColourSupport::Extended
+        );
+        assert_eq!(
+            ColourSupport::from_terminal(Some("1"), Some("screen-256color")),
+            ColourSupport::Extended
+        );
+        assert_eq!(
+            ColourSupport::from_terminal(None, Some("vt100")),
+            ColourSupport::Basic
+        );
+        assert_eq!(
+            ColourSupport::from_terminal(Some("truecolor"), Some("dumb")),
+            ColourSupport::None
+        );
+        assert_eq!(
+            ColourSupport::from_terminal(None, Some("dumb")),
+            ColourSupport::None
+        );
+    }
+}
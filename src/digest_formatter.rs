@@ -0,0 +1,176 @@
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::abbreviation::write_abbreviated;
+use crate::base_line::{format_to_string, Line};
+use crate::checksum_line::ChecksumLine;
+use crate::format_options::FormatOptions;
+use crate::grouping::GroupingWriter;
+
+/// Formats checksum digests.
+///
+/// Unlike `Line`, this trait is object safe, so formatters can be chosen at
+/// runtime, and stored in a `FormatterRegistry` as `dyn DigestFormatter`.
+pub trait DigestFormatter {
+    /// Writes the given checksum string, formatted, to `to`.
+    fn write_digest(&self, digest: &str, to: &mut dyn fmt::Write) -> fmt::Result;
+
+    /// Writes the given checksum string, formatted as configured
+    /// by `options`, to `to`.
+    ///
+    /// By default, `options` are ignored, and this calls `write_digest`.
+    fn write_digest_with_options(
+        &self,
+        digest: &str,
+        to: &mut dyn fmt::Write,
+        options: &FormatOptions,
+    ) -> fmt::Result {
+        let _ = options;
+        self.write_digest(digest, to)
+    }
+
+    /// Whether `write_digest_with_options` spells digests out in whole units,
+    /// such as words or syllables, and groups and abbreviates them by unit
+    /// itself, as configured by `options.grouping` and `options.abbreviation`.
+    ///
+    /// By default, this is `false`, and what's written is grouped and
+    /// abbreviated by visible characters instead.
+    fn writes_units(&self) -> bool {
+        false
+    }
+
+    /// Writes any extra lines to display below the line containing
+    /// `checksum`, as configured by `options`, to `to`.
+    ///
+    /// Lines are separated by `\n`, with no newline after the last,
+    /// even if `options.record_separator` is `RecordSeparator::Nul`.
+    /// By default, nothing is written.
+    fn write_trailer(
+        &self,
+        checksum: &ChecksumLine,
+        to: &mut dyn fmt::Write,
+        options: &FormatOptions,
+    ) -> fmt::Result {
+        let _ = (checksum, to, options);
+        Ok(())
+    }
+
+    /// Formats the given checksum string.
+    fn format_digest(&self, digest: &str) -> String {
+        format_to_string(digest, |digest, to| self.write_digest(digest, to))
+    }
+}
+
+/// Writes `digest`, formatted by `formatter` as configured by `options`,
+/// to `to`, abbreviating it if `options.abbreviation` is set, and splitting
+/// it into groups if `options.grouping` is set.
+pub(crate) fn write_digest(
+    formatter: &dyn DigestFormatter,
+    digest: &str,
+    to: &mut dyn fmt::Write,
+    options: &FormatOptions,
+) -> fmt::Result {
+    // formatters which write whole units know better where groups
+    // and abbreviations can begin and end
+    if formatter.writes_units() {
+        return formatter.write_digest_with_options(digest, to, options);
+    }
+
+    let abbreviation = match options.abbreviation {
+        Some(abbreviation) => abbreviation,
+        None => {
+            return write_grouped(to, options, |to| {
+                formatter.write_digest_with_options(digest, to, options)
+            })
+        }
+    };
+
+    // the whole digest has to be formatted before it can be abbreviated
+    let mut formatted = String::new();
+    formatter.write_digest_with_options(digest, &mut formatted, options)?;
+
+    // the head and tail are grouped separately, so each starts a new group
+    write_abbreviated(digest, &formatted, abbreviation, to, options, |part, to| {
+        write_grouped(to, options, |to| to.write_str(part))
+    })
+}
+
+/// Calls `write` with `to`, wrapped to split what's written into groups
+/// if `options.grouping` is set.
+fn write_grouped(
+    to: &mut dyn fmt::Write,
+    options: &FormatOptions,
+    write: impl FnOnce(&mut dyn fmt::Write) -> fmt::Result,
+) -> fmt::Result {
+    match &options.grouping {
+        Some(grouping) => write(&mut GroupingWriter::new(to, grouping)),
+        None => write(to),
+    }
+}
+
+/// Adapts a `Line` implementation into a `DigestFormatter`,
+/// which formats digests using the `Line`'s `format_hash` function.
+pub struct LineFormatter<L: Line>(PhantomData<fn() -> L>);
+
+impl<L: Line> LineFormatter<L> {
+    /// Creates a `DigestFormatter` for the `Line` type `L`.
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<L: Line> Default for LineFormatter<L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<L: Line> fmt::Debug for LineFormatter<L> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "LineFormatter<{}>", std::any::type_name::<L>())
+    }
+}
+
+impl<L: Line> DigestFormatter for LineFormatter<L> {
+    fn write_digest(&self, digest: &str, to: &mut dyn fmt::Write) -> fmt::Result {
+        L::write_hash(digest, to)
+    }
+
+    fn write_digest_with_options(
+        &self,
+        digest: &str,
+        to: &mut dyn fmt::Write,
+        options: &FormatOptions,
+    ) -> fmt::Result {
+        L::write_hash_with_options(digest, to, options)
+    }
+
+    fn writes_units(&self) -> bool {
+        L::writes_units()
+    }
+
+    fn write_trailer(
+        &self,
+        checksum: &ChecksumLine,
+        to: &mut dyn fmt::Write,
+        options: &FormatOptions,
+    ) -> fmt::Result {
+        L::write_trailer(checksum, to, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn line_formatter_works() {
+        use super::{DigestFormatter, LineFormatter};
+        use crate::{EcojiLine, Line};
+
+        let formatter: &dyn DigestFormatter = &LineFormatter::<EcojiLine>::new();
+
+        assert_eq!(
+            formatter.format_digest("b7527e0e28c09f6f62dd2d4197d5d225"),
+            EcojiLine::format_hash("b7527e0e28c09f6f62dd2d4197d5d225".to_string())
+        );
+    }
+}
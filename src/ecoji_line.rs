@@ -1,11 +1,8 @@
 use std::convert::From;
 use std::fmt;
 use std::fmt::Display;
-use std::iter::FromIterator;
 
-use itertools::Itertools;
-
-use crate::base_line::{FormattableLine, Line};
+use crate::base_line::{format_to_string, write_reencoded, FormattableLine, Line};
 
 #[derive(Debug)]
 /// Line with Ecoji base-1024 emoji encoding.
@@ -16,6 +13,12 @@ impl From<String> for EcojiLine {
     }
 }
 
+impl From<Vec<u8>> for EcojiLine {
+    fn from(contents: Vec<u8>) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
 impl Display for EcojiLine {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         self.to_formatted(formatter)
@@ -27,30 +30,15 @@ impl Line for EcojiLine {
         &self.0
     }
 
-    /// Formats a base16-format hash or digest.
+    fn format_hash(hash: String) -> String {
+        format_to_string(&hash, Self::write_hash)
+    }
+
+    /// Formats a base16 or base64-format hash or digest.
     ///
     /// Data will be encoded using the Ecoji base-1024 emoji encoding.
-    fn format_hash(hash: String) -> String {
-        use std::num::ParseIntError;
-
-        // map over every two characters
-        let result: Result<Vec<u8>, ParseIntError> = hash
-            .chars()
-            .chunks(2)
-            .into_iter()
-            .map(|byte| {
-                let ord_string = String::from_iter(byte);
-                // attempt to parse those two characters as a u8
-                u8::from_str_radix(&ord_string, 16)
-            })
-            .collect();
-
-        match result {
-            // if there was an error at any point, return the original value
-            Err(_) => hash,
-            // otherwise, encode with ecoji
-            Ok(bytes) => ecoji::encode_to_string(&mut &*bytes).unwrap_or(hash),
-        }
+    fn write_hash(hash: &str, to: &mut dyn fmt::Write) -> fmt::Result {
+        write_reencoded(hash, to, |bytes| ecoji::encode_to_string(&mut &*bytes).ok())
     }
 }
 
@@ -90,4 +78,25 @@ mod tests {
         EcojiLine::format_hash("ASDF".to_string());
         EcojiLine::format_hash("😄".to_string());
     }
+
+    #[test]
+    fn coloursum_preserves_line_endings() {
+        use super::EcojiLine;
+        use crate::Line;
+
+        let input: &[u8] = b"MD5 (./src/main.rs) = b7527e0e28c09f6f62dd2d4197d5d225\r\nb7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs\nb7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs";
+        let mut output: Vec<u8> = Vec::new();
+
+        EcojiLine::coloursum(input, &mut output).unwrap();
+
+        let hash = EcojiLine::format_hash("b7527e0e28c09f6f62dd2d4197d5d225".to_string());
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            format!(
+                "MD5 (./src/main.rs) = {0}\r\n{0}  ./src/main.rs\n{0}  ./src/main.rs",
+                hash
+            )
+        );
+    }
 }
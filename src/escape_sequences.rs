@@ -0,0 +1,139 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Where `EscapeTracker` is, in relation to any escape sequences.
+enum EscapeState {
+    /// Reading visible characters.
+    Text,
+    /// Just after an escape character.
+    Escape,
+    /// Within a Control Sequence (`ESC [ ...`), which ends in a character
+    /// from `@` to `~`; `reset` is whether its parameters reset all styles.
+    ControlSequence { reset: bool },
+    /// Within a string, such as a Device Control String (`ESC P ...`),
+    /// which ends with a String Terminator (`ESC \`) or a bell character.
+    String,
+    /// Just after an escape character within a string.
+    StringEscape,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// What part a character plays in formatted text.
+pub(crate) enum CharacterKind {
+    /// A character which is displayed.
+    Visible,
+    /// The escape character which starts an escape sequence.
+    EscapeStart,
+    /// Any other character within an escape sequence.
+    Escaped,
+}
+
+#[derive(Clone, Debug)]
+/// Follows formatted text one character at a time, to tell visible
+/// characters apart from those in ANSI escape sequences, and to keep
+/// track of whether any styles are applied.
+pub(crate) struct EscapeTracker {
+    state: EscapeState,
+    styled: bool,
+}
+
+impl EscapeTracker {
+    pub fn new() -> Self {
+        Self {
+            state: EscapeState::Text,
+            styled: false,
+        }
+    }
+
+    /// Whether any styles, such as colours, are currently applied.
+    pub fn is_styled(&self) -> bool {
+        self.styled
+    }
+
+    /// Moves past `character`, returning what part it plays.
+    pub fn advance(&mut self, character: char) -> CharacterKind {
+        let (state, kind) = match (self.state, character) {
+            (EscapeState::Text, '\x1b') => (EscapeState::Escape, CharacterKind::EscapeStart),
+            (EscapeState::Text, _) => (EscapeState::Text, CharacterKind::Visible),
+            (EscapeState::Escape, '[') => (
+                EscapeState::ControlSequence { reset: true },
+                CharacterKind::Escaped,
+            ),
+            (EscapeState::Escape, 'P' | '_' | ']' | '^' | 'X') => {
+                (EscapeState::String, CharacterKind::Escaped)
+            }
+            (EscapeState::Escape, _) => (EscapeState::Text, CharacterKind::Escaped),
+            (EscapeState::ControlSequence { reset }, '@'..='~') => {
+                // only Select Graphic Rendition sequences change styles
+                if character == 'm' {
+                    self.styled = !reset;
+                }
+                (EscapeState::Text, CharacterKind::Escaped)
+            }
+            (EscapeState::ControlSequence { reset }, _) => (
+                EscapeState::ControlSequence {
+                    reset: reset && matches!(character, '0' | ';'),
+                },
+                CharacterKind::Escaped,
+            ),
+            (EscapeState::String, '\x07') => (EscapeState::Text, CharacterKind::Escaped),
+            (EscapeState::String, '\x1b') => (EscapeState::StringEscape, CharacterKind::Escaped),
+            (EscapeState::String, _) => (EscapeState::String, CharacterKind::Escaped),
+            (EscapeState::StringEscape, '\\') => (EscapeState::Text, CharacterKind::Escaped),
+            (EscapeState::StringEscape, _) => (EscapeState::String, CharacterKind::Escaped),
+        };
+
+        self.state = state;
+        kind
+    }
+}
+
+/// Counts the visible characters in `text`.
+pub(crate) fn visible_length(text: &str) -> usize {
+    let mut tracker = EscapeTracker::new();
+
+    text.chars()
+        .filter(|&character| tracker.advance(character) == CharacterKind::Visible)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn advance_works() {
+        use super::{CharacterKind, EscapeTracker};
+
+        let mut tracker = EscapeTracker::new();
+        let kinds: Vec<_> = "a\x1b[1mb\x1b[0m"
+            .chars()
+            .map(|character| (tracker.advance(character), tracker.is_styled()))
+            .collect();
+
+        assert_eq!(
+            kinds,
+            [
+                (CharacterKind::Visible, false),
+                (CharacterKind::EscapeStart, false),
+                (CharacterKind::Escaped, false),
+                (CharacterKind::Escaped, false),
+                (CharacterKind::Escaped, true),
+                (CharacterKind::Visible, true),
+                (CharacterKind::EscapeStart, true),
+                (CharacterKind::Escaped, true),
+                (CharacterKind::Escaped, true),
+                (CharacterKind::Escaped, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn visible_length_works() {
+        use super::visible_length;
+
+        assert_eq!(visible_length("b7527e0e"), 8);
+        assert_eq!(
+            visible_length("\x1b[38;5;183mb7\x1b[0m\x1b[38;2;1;2;3m52\x1b[0m"),
+            4
+        );
+        assert_eq!(visible_length("😨🏸🤰📺"), 4);
+        assert_eq!(visible_length("\x1b_Ga=T;AAAA\x1b\\ab\x1bPq#0\x07cd"), 4);
+    }
+}
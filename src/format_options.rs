@@ -0,0 +1,69 @@
+use crate::abbreviation::Abbreviation;
+use crate::colour_support::ColourSupport;
+use crate::graphics_support::GraphicsSupport;
+use crate::grouping::Grouping;
+use crate::palette::Palette;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// How records are separated in the input to `Line::coloursum_with_options`.
+pub enum RecordSeparator {
+    /// Records are lines, ending in `\n` or `\r\n`.
+    #[default]
+    Newline,
+    /// Records end in a NUL byte, as produced by
+    /// GNU Coreutils' `sha256sum --zero`.
+    Nul,
+}
+
+impl RecordSeparator {
+    /// The byte which ends each record.
+    pub fn byte(self) -> u8 {
+        match self {
+            RecordSeparator::Newline => b'\n',
+            RecordSeparator::Nul => b'\0',
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+/// Options controlling how `Line::coloursum_with_options` processes its input,
+/// and how formatters format each digest.
+pub struct FormatOptions {
+    /// How records are separated in the input.
+    ///
+    /// Each record's separator is written back out unchanged.
+    pub record_separator: RecordSeparator,
+
+    /// Whether to decode filenames which GNU Coreutils has escaped.
+    ///
+    /// When a filename contains a backslash or newline, GNU Coreutils
+    /// prefixes the line with a backslash and escapes the filename.
+    /// If this is set, the backslash prefix is removed and
+    /// the filename is written in its original form.
+    pub unescape_filenames: bool,
+
+    /// Which colours the colouring formatters use.
+    ///
+    /// Formatters which don't colour digests ignore this.
+    pub palette: Palette,
+
+    /// Which colours the terminal supports.
+    ///
+    /// Colouring formatters downgrade their colours to the closest
+    /// ones available, or leave digests uncoloured if there are none.
+    pub colour_support: ColourSupport,
+
+    /// Which protocol the terminal can display images with, if any.
+    ///
+    /// Formatters which draw images fall back to text without one.
+    pub graphics: GraphicsSupport,
+
+    /// How to split formatted digests into groups, if at all.
+    pub grouping: Option<Grouping>,
+
+    /// How to abbreviate formatted digests, if at all.
+    ///
+    /// Only the start and end of each digest are shown, with an ellipsis
+    /// in between. Filenames are left intact.
+    pub abbreviation: Option<Abbreviation>,
+}
@@ -0,0 +1,200 @@
+use std::fmt;
+
+use crate::digest_formatter::{DigestFormatter, LineFormatter};
+use crate::{
+    ANSIBlockLine, ANSIColouredLine, BIP39WordLine, Base58Line, Base64Line, BasicColouredLine,
+    BubbleBabbleLine, ColourBarLine, EcojiLine, HexColourBarLine, HexDigitLine, IdenticonLine,
+    NATOPhoneticLine, NixBase32Line, OnePasswordLine, PGPWordLine, PlainLine, ProquintLine,
+    RandomartLine, TruecolourLine, ZBase32Line,
+};
+
+/// A collection of `DigestFormatter`s, looked up by name.
+///
+/// The `Default` registry contains coloursum's built-in formatters;
+/// further formatters may be added with `register`.
+///
+/// ```rust
+/// use std::fmt;
+///
+/// use coloursum::{DigestFormatter, FormatterRegistry};
+///
+/// struct Shouting;
+///
+/// impl DigestFormatter for Shouting {
+///     fn write_digest(&self, digest: &str, to: &mut dyn fmt::Write) -> fmt::Result {
+///         to.write_str(&digest.to_uppercase())
+///     }
+/// }
+///
+/// let mut registry = FormatterRegistry::default();
+/// registry.register("shouting", Shouting);
+///
+/// assert_eq!(
+///     registry.get("shouting").unwrap().format_digest("b7527e0e"),
+///     "B7527E0E"
+/// );
+/// ```
+pub struct FormatterRegistry {
+    formatters: Vec<(String, Box<dyn DigestFormatter>)>,
+}
+
+impl FormatterRegistry {
+    /// Creates an empty registry, with no formatters at all.
+    pub fn new() -> Self {
+        Self {
+            formatters: Vec::new(),
+        }
+    }
+
+    /// Adds `formatter` to the registry, under `name`.
+    ///
+    /// If a formatter was already registered with the same name,
+    /// it is replaced.
+    pub fn register<F: DigestFormatter + 'static>(
+        &mut self,
+        name: impl Into<String>,
+        formatter: F,
+    ) {
+        let name = name.into();
+        let formatter: Box<dyn DigestFormatter> = Box::new(formatter);
+
+        match self
+            .formatters
+            .iter_mut()
+            .find(|(registered_name, _)| *registered_name == name)
+        {
+            Some((_, registered_formatter)) => *registered_formatter = formatter,
+            None => self.formatters.push((name, formatter)),
+        }
+    }
+
+    /// Looks up the formatter registered under `name`.
+    pub fn get(&self, name: &str) -> Option<&dyn DigestFormatter> {
+        self.formatters
+            .iter()
+            .find(|(registered_name, _)| registered_name == name)
+            .map(|(_, formatter)| formatter.as_ref())
+    }
+
+    /// The names of all registered formatters, in the order they were registered.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.formatters.iter().map(|(name, _)| name.as_str())
+    }
+}
+
+impl Default for FormatterRegistry {
+    /// Creates a registry containing coloursum's built-in formatters.
+    fn default() -> Self {
+        let mut registry = Self::new();
+
+        registry.register("ansi-colours", LineFormatter::<ANSIColouredLine>::new());
+        registry.register("ecoji", LineFormatter::<EcojiLine>::new());
+        registry.register("1password", LineFormatter::<OnePasswordLine>::new());
+        registry.register("truecolour", LineFormatter::<TruecolourLine>::new());
+        registry.register("basic-colours", LineFormatter::<BasicColouredLine>::new());
+        registry.register("hex-digits", LineFormatter::<HexDigitLine>::new());
+        registry.register("ansi-blocks", LineFormatter::<ANSIBlockLine>::new());
+        registry.register("plain", LineFormatter::<PlainLine>::new());
+        registry.register("pgp-words", LineFormatter::<PGPWordLine>::new());
+        registry.register("bubble-babble", LineFormatter::<BubbleBabbleLine>::new());
+        registry.register("proquint", LineFormatter::<ProquintLine>::new());
+        registry.register("randomart", LineFormatter::<RandomartLine>::new());
+        registry.register("identicon", LineFormatter::<IdenticonLine>::new());
+        registry.register("colour-bar", LineFormatter::<ColourBarLine>::new());
+        registry.register("hex-colour-bar", LineFormatter::<HexColourBarLine>::new());
+        registry.register("words", LineFormatter::<BIP39WordLine>::new());
+        registry.register("nato", LineFormatter::<NATOPhoneticLine>::new());
+        registry.register("base64", LineFormatter::<Base64Line>::new());
+        registry.register("nix-base32", LineFormatter::<NixBase32Line>::new());
+        registry.register("base58", LineFormatter::<Base58Line>::new());
+        registry.register("z-base-32", LineFormatter::<ZBase32Line>::new());
+
+        registry
+    }
+}
+
+impl fmt::Debug for FormatterRegistry {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.debug_list().entries(self.names()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn default_works() {
+        use super::FormatterRegistry;
+
+        let registry = FormatterRegistry::default();
+        let hash = "b7527e0e28c09f6f62dd2d4197d5d225";
+
+        assert_eq!(
+            registry.names().collect::<Vec<_>>(),
+            [
+                "ansi-colours",
+                "ecoji",
+                "1password",
+                "truecolour",
+                "basic-colours",
+                "hex-digits",
+                "ansi-blocks",
+                "plain",
+                "pgp-words",
+                "bubble-babble",
+                "proquint",
+                "randomart",
+                "identicon",
+                "colour-bar",
+                "hex-colour-bar",
+                "words",
+                "nato",
+                "base64",
+                "nix-base32",
+                "base58",
+                "z-base-32"
+            ]
+        );
+
+        for name in registry.names() {
+            let formatted = registry
+                .get(name)
+                .unwrap_or_else(|| panic!("{} isn't registered", name))
+                .format_digest(hash);
+
+            // these leave the digest itself as it is
+            if name == "plain" || name == "randomart" {
+                assert_eq!(formatted, hash, "{} changed the digest", name);
+            } else {
+                assert_ne!(formatted, hash, "{} didn't format the digest", name);
+            }
+        }
+
+        assert!(registry.get("rot13").is_none());
+    }
+
+    #[test]
+    fn register_replaces_existing_formatters() {
+        use super::FormatterRegistry;
+        use crate::{DigestFormatter, EcojiLine, LineFormatter};
+
+        struct Unchanged;
+
+        impl DigestFormatter for Unchanged {
+            fn write_digest(&self, digest: &str, to: &mut dyn std::fmt::Write) -> std::fmt::Result {
+                to.write_str(digest)
+            }
+        }
+
+        let mut registry = FormatterRegistry::new();
+        assert_eq!(registry.names().count(), 0);
+
+        registry.register("custom", LineFormatter::<EcojiLine>::new());
+        registry.register("custom", Unchanged);
+
+        assert_eq!(registry.names().collect::<Vec<_>>(), ["custom"]);
+        assert_eq!(
+            registry.get("custom").unwrap().format_digest("b7527e0e"),
+            "b7527e0e"
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use std::env;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// Which protocol, if any, a terminal can display images with.
+///
+/// Formatters which draw images fall back to text when there's none.
+/// The default, `None`, keeps output to text alone.
+pub enum GraphicsSupport {
+    /// No images at all.
+    #[default]
+    None,
+    /// DEC Sixel graphics.
+    Sixel,
+    /// The Kitty terminal graphics protocol.
+    Kitty,
+}
+
+impl GraphicsSupport {
+    /// Detects which protocol the current terminal supports, from the
+    /// `TERM` and `TERM_PROGRAM` environment variables.
+    ///
+    /// This doesn't check whether output is going to a terminal at all;
+    /// that's up to the caller.
+    pub fn detect() -> Self {
+        let term = env::var("TERM").ok();
+        let term_program = env::var("TERM_PROGRAM").ok();
+
+        Self::from_terminal(term.as_deref(), term_program.as_deref())
+    }
+
+    /// Works out which protocol a terminal supports, from the values
+    /// of its `TERM` and `TERM_PROGRAM` environment variables.
+    ///
+    /// Terminals are only recognised if they're known to support a protocol;
+    /// asking the terminal itself would mean reading its reply from standard
+    /// input, which is where checksums are read from.
+    pub fn from_terminal(term: Option<&str>, term_program: Option<&str>) -> Self {
+        match (term, term_program) {
+            // multiplexers don't pass images through to the outer terminal
+            (Some(term), _) if term.starts_with("screen") || term.starts_with("tmux") => {
+                GraphicsSupport::None
+            }
+            (Some("xterm-kitty"), _) | (_, Some("WezTerm" | "ghostty")) => GraphicsSupport::Kitty,
+            (Some(term), _) if term.starts_with("foot") || term.starts_with("mlterm") => {
+                GraphicsSupport::Sixel
+            }
+            (_, Some("iTerm.app")) => GraphicsSupport::Sixel,
+            _ => GraphicsSupport::None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn from_terminal_works() {
+        use super::GraphicsSupport;
+
+        assert_eq!(
+            GraphicsSupport::from_terminal(Some("xterm-kitty"), None),
+            GraphicsSupport::Kitty
+        );
+        assert_eq!(
+            GraphicsSupport::from_terminal(Some("xterm-256color"), Some("WezTerm")),
+            GraphicsSupport::Kitty
+        );
+        assert_eq!(
+            GraphicsSupport::from_terminal(Some("foot"), None),
+            GraphicsSupport::Sixel
+        );
+        assert_eq!(
+            GraphicsSupport::from_terminal(Some("xterm-256color"), Some("iTerm.app")),
+            GraphicsSupport::Sixel
+        );
+        assert_eq!(
+            GraphicsSupport::from_terminal(Some("xterm-256color"), Some("Apple_Terminal")),
+            GraphicsSupport::None
+        );
+        assert_eq!(
+            GraphicsSupport::from_terminal(Some("tmux-256color"), Some("WezTerm")),
+            GraphicsSupport::None
+        );
+        assert_eq!(
+            GraphicsSupport::from_terminal(None, None),
+            GraphicsSupport::None
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use std::fmt;
+
+use crate::escape_sequences::{CharacterKind, EscapeTracker};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// How to split formatted digests into groups, to make them easier to read.
+///
+/// ```rust
+/// use coloursum::{FormatOptions, Grouping, OnePasswordLine, Line};
+///
+/// let line = OnePasswordLine::from("MD5 (./src/main.rs) = b7527e0e28c09f6f62dd2d4197d5d225".to_string());
+/// let mut output = String::new();
+///
+/// line.to_formatted_with_options(
+///     &mut output,
+///     &FormatOptions {
+///         grouping: Some(Grouping::new(2, ":")),
+///         colour_support: coloursum::ColourSupport::None,
+///         ..FormatOptions::default()
+///     },
+/// )
+/// .unwrap();
+///
+/// assert_eq!(
+///     output,
+///     "MD5 (./src/main.rs) = b7:52:7e:0e:28:c0:9f:6f:62:dd:2d:41:97:d5:d2:25"
+/// );
+/// ```
+pub struct Grouping {
+    /// How many characters to put in each group.
+    ///
+    /// A size of `0` leaves digests ungrouped.
+    pub size: usize,
+    /// What to put between groups.
+    pub separator: String,
+}
+
+impl Grouping {
+    /// Creates a `Grouping` which puts `separator`
+    /// between each group of `size` characters.
+    pub fn new(size: usize, separator: impl Into<String>) -> Self {
+        Self {
+            size,
+            separator: separator.into(),
+        }
+    }
+}
+
+/// Writes to `inner`, inserting a separator between groups of visible
+/// characters, while passing ANSI escape sequences through untouched.
+///
+/// Separators are written once any styles applied to the preceding group
+/// have been reset, so that they aren't coloured in.
+pub(crate) struct GroupingWriter<'a> {
+    inner: &'a mut dyn fmt::Write,
+    grouping: &'a Grouping,
+    /// How many visible characters have been written in the current group.
+    group_length: usize,
+    /// Whether a separator is due before the next group.
+    pending: bool,
+    escapes: EscapeTracker,
+}
+
+impl<'a> GroupingWriter<'a> {
+    pub fn new(inner: &'a mut dyn fmt::Write, grouping: &'a Grouping) -> Self {
+        Self {
+            inner,
+            grouping,
+            group_length: 0,
+            pending: false,
+            escapes: EscapeTracker::new(),
+        }
+    }
+
+    /// Writes the separator, if one is due.
+    fn write_pending_separator(&mut self) -> fmt::Result {
+        if self.pending {
+            self.pending = false;
+            self.inner.write_str(&self.grouping.separator)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Write for GroupingWriter<'a> {
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+        if self.grouping.size == 0 {
+            return self.inner.write_str(string);
+        }
+
+        for (offset, character) in string.char_indices() {
+            // whether the styles of the previous group are still applied
+            let styled = self.escapes.is_styled();
+
+            match self.escapes.advance(character) {
+                CharacterKind::EscapeStart if !styled => {
+                    // start the next group before it gets styled
+                    self.write_pending_separator()?;
+                }
+                CharacterKind::Visible => {
+                    self.write_pending_separator()?;
+                    self.group_length += 1;
+                    if self.group_length == self.grouping.size {
+                        self.group_length = 0;
+                        self.pending = true;
+                    }
+                }
+                _ => {}
+            }
+
+            self.inner
+                .write_str(&string[offset..offset + character.len_utf8()])?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    fn group(string: &str, grouping: &super::Grouping) -> String {
+        use super::GroupingWriter;
+        use std::fmt::Write;
+
+        let mut output = String::new();
+        GroupingWriter::new(&mut output, grouping)
+            .write_str(string)
+            .unwrap();
+        output
+    }
+
+    #[test]
+    fn grouping_works() {
+        use super::Grouping;
+
+        assert_eq!(
+            group("b7527e0e28c09f6f", &Grouping::new(4, " ")),
+            "b752 7e0e 28c0 9f6f"
+        );
+        assert_eq!(
+            group("b7527e0e28c09f6", &Grouping::new(4, " ")),
+            "b752 7e0e 28c0 9f6"
+        );
+        assert_eq!(group("b7527e0e", &Grouping::new(2, ":")), "b7:52:7e:0e");
+        assert_eq!(group("😨🏸🤰📺", &Grouping::new(2, "-")), "😨🏸-🤰📺");
+        assert_eq!(group("b7527e0e", &Grouping::new(0, " ")), "b7527e0e");
+    }
+
+    #[test]
+    fn grouping_leaves_styles_out_of_separators() {
+        use super::Grouping;
+
+        assert_eq!(
+            group(
+                "\x1b[38;5;183mb7\x1b[0m\x1b[38;5;82m52\x1b[0m",
+                &Grouping::new(2, " ")
+            ),
+            "\x1b[38;5;183mb7\x1b[0m \x1b[38;5;82m52\x1b[0m"
+        );
+        assert_eq!(
+            group(
+                "b\x1b[38;5;4m7\x1b[0m\x1b[38;5;4m5\x1b[0m2",
+                &Grouping::new(1, " ")
+            ),
+            "b \x1b[38;5;4m7\x1b[0m \x1b[38;5;4m5\x1b[0m 2"
+        );
+        // styles which carry on into the next group are left alone
+        assert_eq!(
+            group("\x1b[1mb752\x1b[m", &Grouping::new(2, " ")),
+            "\x1b[1mb7 52\x1b[m"
+        );
+        // as are other kinds of escape sequences
+        assert_eq!(
+            group(
+                "\x1b_Ga=T;AAAA\x1b\\ab\x1bPq#0\x07cd",
+                &Grouping::new(2, " ")
+            ),
+            "\x1b_Ga=T;AAAA\x1b\\ab \x1bPq#0\x07cd"
+        );
+    }
+}
@@ -0,0 +1,271 @@
+use std::convert::From;
+use std::fmt;
+use std::fmt::Display;
+
+use crate::base_line::{format_to_string, FormattableLine, Line};
+use crate::colour::{write_block, write_painted, TermColour};
+use crate::colour_support::ColourSupport;
+use crate::format_options::FormatOptions;
+
+/// The colour of each hexadecimal digit, from `0` to `f`.
+///
+/// These are 16 of Sasha Trubetskoy's "20 simple, distinct colours",
+/// leaving out those too dark or too light to read on common themes,
+/// and ordered so that consecutive digits have unrelated colours.
+const DIGIT_COLOURS: [(u8, u8, u8); 16] = [
+    (230, 25, 75),   // red
+    (66, 212, 244),  // cyan
+    (255, 225, 25),  // yellow
+    (145, 30, 180),  // purple
+    (60, 180, 75),   // green
+    (250, 190, 212), // pink
+    (67, 99, 216),   // blue
+    (245, 130, 49),  // orange
+    (70, 153, 144),  // teal
+    (240, 50, 230),  // magenta
+    (191, 239, 69),  // lime
+    (154, 99, 36),   // brown
+    (220, 190, 255), // lavender
+    (128, 128, 0),   // olive
+    (170, 255, 195), // mint
+    (169, 169, 169), // grey
+];
+
+/// The basic ANSI colour of each hexadecimal digit, from `0` to `f`,
+/// for terminals which can't display anything more.
+///
+/// Each of the 8 basic colours is used once as it is, and once in its
+/// bright variant, rather than downgrading `DIGIT_COLOURS`, which would
+/// leave several digits the same colour. Black and bright white can't
+/// be seen on dark and light themes respectively, so digits with those
+/// colours are written as blocks of them instead.
+const BASIC_DIGIT_COLOURS: [u8; 16] = [
+    1,  // red
+    14, // bright cyan
+    3,  // yellow
+    12, // bright blue
+    2,  // green
+    13, // bright magenta
+    8,  // bright black
+    11, // bright yellow
+    4,  // blue
+    9,  // bright red
+    6,  // cyan
+    7,  // white
+    5,  // magenta
+    10, // bright green
+    0,  // black
+    15, // bright white
+];
+
+#[derive(Debug)]
+/// Line with formatting which colours each hexadecimal digit
+/// with its own colour, so the same digit always looks the same.
+pub struct HexDigitLine(FormattableLine);
+impl From<String> for HexDigitLine {
+    fn from(contents: String) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl From<Vec<u8>> for HexDigitLine {
+    fn from(contents: Vec<u8>) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl Display for HexDigitLine {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.to_formatted(formatter)
+    }
+}
+
+impl Line for HexDigitLine {
+    fn get_line(&self) -> &FormattableLine {
+        &self.0
+    }
+
+    fn format_hash(hash: String) -> String {
+        format_to_string(&hash, Self::write_hash)
+    }
+
+    /// Formats a base16-format hash or digest.
+    ///
+    /// Each hexadecimal digit will be coloured with one of 16 distinct colours,
+    /// regardless of its position, or whether it is upper or lower case.
+    fn write_hash(hash: &str, to: &mut dyn fmt::Write) -> fmt::Result {
+        Self::write_hash_with_options(hash, to, &FormatOptions::default())
+    }
+
+    /// Formats a base16-format hash or digest.
+    ///
+    /// Each hexadecimal digit will be coloured with one of 16 distinct
+    /// colours, or, if `options` selects a palette other than the standard
+    /// one, the palette's colour for that digit. Where only the basic ANSI
+    /// colours are supported, each digit gets one of those instead,
+    /// whichever palette is selected.
+    fn write_hash_with_options(
+        hash: &str,
+        to: &mut dyn fmt::Write,
+        options: &FormatOptions,
+    ) -> fmt::Result {
+        // if there are any non-base16 characters, write the original value
+        if !hash.bytes().all(|character| character.is_ascii_hexdigit()) {
+            return to.write_str(hash);
+        }
+
+        for (offset, character) in hash.char_indices() {
+            // these are all base16 characters, so this can't fail
+            let digit = character.to_digit(16).unwrap_or_default() as u8;
+            let text = &hash[offset..offset + 1];
+
+            let colour = if options.colour_support == ColourSupport::Basic {
+                TermColour::Fixed(BASIC_DIGIT_COLOURS[usize::from(digit)])
+            } else {
+                let (red, green, blue) = options
+                    .palette
+                    .digit_colour(digit)
+                    .unwrap_or(DIGIT_COLOURS[usize::from(digit)]);
+
+                TermColour::Rgb(red, green, blue)
+            };
+
+            match colour {
+                // black and bright white text can't be seen on every theme
+                TermColour::Fixed(0 | 15) => write_block(to, colour, text, options.colour_support)?,
+                _ => write_painted(to, colour, text, options.colour_support)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn display_works() {
+        use super::HexDigitLine;
+
+        assert_eq!(
+            format!("{}", HexDigitLine::from("b7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs".to_string())),
+            "\u{1b}[38;2;154;99;36mb\u{1b}[0m\u{1b}[38;2;245;130;49m7\u{1b}[0m\u{1b}[38;2;250;190;212m5\u{1b}[0m\u{1b}[38;2;255;225;25m2\u{1b}[0m\u{1b}[38;2;245;130;49m7\u{1b}[0m\u{1b}[38;2;170;255;195me\u{1b}[0m\u{1b}[38;2;230;25;75m0\u{1b}[0m\u{1b}[38;2;170;255;195me\u{1b}[0m\u{1b}[38;2;255;225;25m2\u{1b}[0m\u{1b}[38;2;70;153;144m8\u{1b}[0m\u{1b}[38;2;220;190;255mc\u{1b}[0m\u{1b}[38;2;230;25;75m0\u{1b}[0m\u{1b}[38;2;240;50;230m9\u{1b}[0m\u{1b}[38;2;169;169;169mf\u{1b}[0m\u{1b}[38;2;67;99;216m6\u{1b}[0m\u{1b}[38;2;169;169;169mf\u{1b}[0m\u{1b}[38;2;67;99;216m6\u{1b}[0m\u{1b}[38;2;255;225;25m2\u{1b}[0m\u{1b}[38;2;128;128;0md\u{1b}[0m\u{1b}[38;2;128;128;0md\u{1b}[0m\u{1b}[38;2;255;225;25m2\u{1b}[0m\u{1b}[38;2;128;128;0md\u{1b}[0m\u{1b}[38;2;60;180;75m4\u{1b}[0m\u{1b}[38;2;66;212;244m1\u{1b}[0m\u{1b}[38;2;240;50;230m9\u{1b}[0m\u{1b}[38;2;245;130;49m7\u{1b}[0m\u{1b}[38;2;128;128;0md\u{1b}[0m\u{1b}[38;2;250;190;212m5\u{1b}[0m\u{1b}[38;2;128;128;0md\u{1b}[0m\u{1b}[38;2;255;225;25m2\u{1b}[0m\u{1b}[38;2;255;225;25m2\u{1b}[0m\u{1b}[38;2;250;190;212m5\u{1b}[0m  ./src/main.rs"
+        )
+    }
+
+    #[test]
+    fn format_hash_works() {
+        use super::HexDigitLine;
+        use crate::Line;
+
+        assert_eq!(
+            HexDigitLine::format_hash("4ff4".to_string()),
+            "\u{1b}[38;2;60;180;75m4\u{1b}[0m\u{1b}[38;2;169;169;169mf\u{1b}[0m\u{1b}[38;2;169;169;169mf\u{1b}[0m\u{1b}[38;2;60;180;75m4\u{1b}[0m"
+        );
+
+        // the same digit looks the same, whatever its case
+        assert_eq!(
+            HexDigitLine::format_hash("B7527E0E".to_string()).to_lowercase(),
+            HexDigitLine::format_hash("b7527e0e".to_string())
+        );
+    }
+
+    #[test]
+    fn format_hash_doesnt_crash_on_non_base16_characters() {
+        use super::HexDigitLine;
+        use crate::Line;
+
+        assert_eq!(HexDigitLine::format_hash("ASDF".to_string()), "ASDF");
+        assert_eq!(HexDigitLine::format_hash("😄".to_string()), "😄");
+    }
+
+    #[test]
+    fn digit_colours_are_distinct() {
+        use super::DIGIT_COLOURS;
+        use crate::colour::distance;
+
+        for (index, first) in DIGIT_COLOURS.iter().enumerate() {
+            for second in &DIGIT_COLOURS[index + 1..] {
+                assert!(
+                    distance(*first, *second) > 2_500,
+                    "{:?} and {:?} are too similar",
+                    first,
+                    second
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn write_hash_with_options_uses_palette() {
+        use super::HexDigitLine;
+        use crate::{ColourSupport, FormatOptions, Line, Palette};
+
+        let write = |palette, colour_support| {
+            let mut output = String::new();
+            HexDigitLine::write_hash_with_options(
+                "4af",
+                &mut output,
+                &FormatOptions {
+                    palette,
+                    colour_support,
+                    ..FormatOptions::default()
+                },
+            )
+            .unwrap();
+            output
+        };
+
+        assert_eq!(
+            write(Palette::Deuteranopia, ColourSupport::Truecolour),
+            "\u{1b}[38;2;137;195;255m4\u{1b}[0m\u{1b}[38;2;145;124;103ma\u{1b}[0m\u{1b}[38;2;140;161;183mf\u{1b}[0m"
+        );
+        assert_eq!(
+            write(Palette::Greyscale, ColourSupport::Extended),
+            "\u{1b}[38;5;251m4\u{1b}[0m\u{1b}[38;5;244ma\u{1b}[0m\u{1b}[38;5;247mf\u{1b}[0m"
+        );
+    }
+
+    #[test]
+    fn write_hash_with_options_uses_basic_colours() {
+        use super::HexDigitLine;
+        use crate::{ColourSupport, FormatOptions, Line, Palette};
+
+        let write = |hash, palette| {
+            let mut output = String::new();
+            HexDigitLine::write_hash_with_options(
+                hash,
+                &mut output,
+                &FormatOptions {
+                    palette,
+                    colour_support: ColourSupport::Basic,
+                    ..FormatOptions::default()
+                },
+            )
+            .unwrap();
+            output
+        };
+
+        assert_eq!(
+            write("7ade", Palette::Standard),
+            "\u{1b}[93m7\u{1b}[0m\u{1b}[36ma\u{1b}[0m\u{1b}[92md\u{1b}[0m\u{1b}[40;97me\u{1b}[0m"
+        );
+        // the basic colours are used whichever palette is selected
+        assert_eq!(
+            write("7ade", Palette::Tritanopia),
+            write("7ade", Palette::Standard)
+        );
+        assert_eq!(write("f", Palette::Standard), "\u{1b}[107;30mf\u{1b}[0m");
+    }
+
+    #[test]
+    fn basic_digit_colours_are_distinct() {
+        use super::BASIC_DIGIT_COLOURS;
+        use std::collections::HashSet;
+
+        assert_eq!(
+            BASIC_DIGIT_COLOURS.iter().collect::<HashSet<_>>().len(),
+            BASIC_DIGIT_COLOURS.len()
+        );
+    }
+}
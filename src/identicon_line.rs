@@ -0,0 +1,301 @@
+use std::convert::From;
+use std::fmt;
+use std::fmt::Display;
+
+use base64::Engine;
+
+use crate::ansi_coloured_line::ANSIColouredLine;
+use crate::base_line::{format_to_string, FormattableLine, Line};
+use crate::checksum_line::decode_digest;
+use crate::format_options::FormatOptions;
+use crate::graphics_support::GraphicsSupport;
+use crate::truecolour_line::byte_colour;
+
+/// How many cells wide and tall the identicon's pattern is.
+const GRID_SIZE: usize = 5;
+/// How many pixels wide and tall each cell of the pattern is.
+const CELL_SIZE: usize = 2;
+/// How many pixels wide and tall the image is, with half a cell
+/// of empty space around the pattern.
+const IMAGE_SIZE: usize = (GRID_SIZE + 1) * CELL_SIZE;
+/// How many terminal columns the image takes up.
+const IMAGE_COLUMNS: usize = 2;
+
+/// A symmetric pattern of filled cells, and the colour to fill them with.
+struct Identicon {
+    cells: [[bool; GRID_SIZE]; GRID_SIZE],
+    colour: (u8, u8, u8),
+}
+
+impl Identicon {
+    /// Creates the identicon for `bytes`, if there are enough of them.
+    ///
+    /// Every byte is XOR-folded into three, so that the whole digest
+    /// decides the image. The first folded byte picks the colour, and the
+    /// next 15 bits fill in the left three columns, which are mirrored
+    /// onto the right two.
+    fn new(bytes: &[u8], options: &FormatOptions) -> Option<Self> {
+        if bytes.len() < 3 {
+            return None;
+        }
+
+        let mut folded = [0_u8; 3];
+        for chunk in bytes.chunks(3) {
+            for (folded, byte) in folded.iter_mut().zip(chunk) {
+                *folded ^= byte;
+            }
+        }
+
+        let [colour_byte, low, high] = folded;
+        let bits = u16::from_le_bytes([low, high]);
+
+        let mut cells = [[false; GRID_SIZE]; GRID_SIZE];
+        for (row, cells) in cells.iter_mut().enumerate() {
+            // the middle column of the odd-sized grid isn't mirrored
+            for column in 0..GRID_SIZE / 2 + 1 {
+                let filled = bits & (1 << (row * 3 + column)) != 0;
+                cells[column] = filled;
+                cells[GRID_SIZE - 1 - column] = filled;
+            }
+        }
+
+        let colour = options
+            .palette
+            .colour(colour_byte)
+            .unwrap_or_else(|| byte_colour(colour_byte))
+            .to_rgb();
+
+        Some(Self { cells, colour })
+    }
+
+    /// Whether the pixel at `x`, `y` is part of a filled cell.
+    fn is_filled(&self, x: usize, y: usize) -> bool {
+        // skip the half cell of space around the pattern
+        let margin = CELL_SIZE / 2;
+        if x < margin || y < margin || x >= IMAGE_SIZE - margin || y >= IMAGE_SIZE - margin {
+            return false;
+        }
+
+        self.cells[(y - margin) / CELL_SIZE][(x - margin) / CELL_SIZE]
+    }
+
+    /// Writes the identicon as a Sixel image, leaving
+    /// the cursor just after where the image is drawn.
+    fn write_sixel(&self, to: &mut dyn fmt::Write) -> fmt::Result {
+        let percent = |value: u8| (u32::from(value) * 100 + 127) / 255;
+        let (red, green, blue) = self.colour;
+
+        // save the cursor position, as terminals differ in where they
+        // leave it, then start an image with a transparent background
+        write!(
+            to,
+            "\x1b7\x1bP0;1;0q\"1;1;{size};{size}#1;2;{};{};{}#1",
+            percent(red),
+            percent(green),
+            percent(blue),
+            size = IMAGE_SIZE
+        )?;
+
+        // each character draws six pixels, one above the other
+        for top in (0..IMAGE_SIZE).step_by(6) {
+            if top > 0 {
+                to.write_char('-')?;
+            }
+
+            for x in 0..IMAGE_SIZE {
+                let sixel = (0..6)
+                    .filter(|row| self.is_filled(x, top + row))
+                    .fold(0, |sixel, row| sixel | (1 << row));
+                to.write_char(char::from(b'?' + sixel))?;
+            }
+        }
+
+        // move past the image without overwriting it
+        write!(to, "\x1b\\\x1b8\x1b[{}C", IMAGE_COLUMNS + 1)
+    }
+
+    /// Writes the identicon as a Kitty graphics protocol image, leaving
+    /// the cursor just after where the image is drawn.
+    fn write_kitty(&self, to: &mut dyn fmt::Write) -> fmt::Result {
+        let (red, green, blue) = self.colour;
+        let mut pixels = Vec::with_capacity(IMAGE_SIZE * IMAGE_SIZE * 4);
+
+        for y in 0..IMAGE_SIZE {
+            for x in 0..IMAGE_SIZE {
+                if self.is_filled(x, y) {
+                    pixels.extend_from_slice(&[red, green, blue, 255]);
+                } else {
+                    pixels.extend_from_slice(&[0, 0, 0, 0]);
+                }
+            }
+        }
+
+        // the image is small enough to send in one chunk; `q=2` stops the
+        // terminal from replying, and `c` and `r` scale it to fit the line
+        write!(
+            to,
+            "\x1b_Ga=T,f=32,s={size},v={size},c={},r=1,q=2;{}\x1b\\\x1b[1C",
+            IMAGE_COLUMNS,
+            base64::engine::general_purpose::STANDARD.encode(pixels),
+            size = IMAGE_SIZE
+        )
+    }
+}
+
+#[derive(Debug)]
+/// Line with a small, symmetric identicon image drawn before the digest,
+/// for terminals which can display images.
+pub struct IdenticonLine(FormattableLine);
+impl From<String> for IdenticonLine {
+    fn from(contents: String) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl From<Vec<u8>> for IdenticonLine {
+    fn from(contents: Vec<u8>) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl Display for IdenticonLine {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.to_formatted(formatter)
+    }
+}
+
+impl Line for IdenticonLine {
+    fn get_line(&self) -> &FormattableLine {
+        &self.0
+    }
+
+    fn format_hash(hash: String) -> String {
+        format_to_string(&hash, Self::write_hash)
+    }
+
+    /// Formats a base16-format hash or digest.
+    ///
+    /// Without any way of displaying images, this is the same
+    /// as `ANSIColouredLine`'s formatting.
+    fn write_hash(hash: &str, to: &mut dyn fmt::Write) -> fmt::Result {
+        Self::write_hash_with_options(hash, to, &FormatOptions::default())
+    }
+
+    /// Formats a base16-format hash or digest.
+    ///
+    /// If `options.graphics` names a protocol the terminal supports, a
+    /// 5 by 5 identicon, in the style of GitHub's generated avatars, is drawn
+    /// in front of the digest. The digest itself is formatted the same
+    /// way as `ANSIColouredLine` formats it.
+    fn write_hash_with_options(
+        hash: &str,
+        to: &mut dyn fmt::Write,
+        options: &FormatOptions,
+    ) -> fmt::Result {
+        let identicon = match options.graphics {
+            GraphicsSupport::None => None,
+            _ => decode_digest(hash).and_then(|bytes| Identicon::new(&bytes, options)),
+        };
+
+        match (identicon, options.graphics) {
+            (Some(identicon), GraphicsSupport::Sixel) => identicon.write_sixel(to)?,
+            (Some(identicon), GraphicsSupport::Kitty) => identicon.write_kitty(to)?,
+            _ => {}
+        }
+
+        ANSIColouredLine::write_hash_with_options(hash, to, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn format_hash_falls_back_to_ansi_colours() {
+        use super::IdenticonLine;
+        use crate::{ANSIColouredLine, Line};
+
+        assert_eq!(
+            IdenticonLine::format_hash("b7527e0e28c09f6f62dd2d4197d5d225".to_string()),
+            ANSIColouredLine::format_hash("b7527e0e28c09f6f62dd2d4197d5d225".to_string())
+        );
+    }
+
+    #[test]
+    fn identicon_is_symmetric() {
+        use super::{Identicon, GRID_SIZE};
+        use crate::FormatOptions;
+
+        let identicon = Identicon::new(&[0xb7, 0x52, 0x7e], &FormatOptions::default()).unwrap();
+
+        for row in &identicon.cells {
+            for column in 0..GRID_SIZE {
+                assert_eq!(row[column], row[GRID_SIZE - 1 - column]);
+            }
+        }
+        assert_eq!(
+            identicon.cells,
+            [
+                [false, true, false, true, false],
+                [false, true, false, true, false],
+                [true, false, false, false, true],
+                [true, true, true, true, true],
+                [true, true, true, true, true],
+            ]
+        );
+
+        // there aren't enough bytes for a pattern
+        assert!(Identicon::new(&[0xb7, 0x52], &FormatOptions::default()).is_none());
+    }
+
+    #[test]
+    fn identicon_depends_on_every_byte() {
+        use super::Identicon;
+        use crate::FormatOptions;
+
+        let options = FormatOptions::default();
+        let identicon = Identicon::new(&[0xb7, 0x52, 0x7e, 0x0e], &options).unwrap();
+        // the same first three bytes, but a different fourth byte
+        let other = Identicon::new(&[0xb7, 0x52, 0x7e, 0x0f], &options).unwrap();
+
+        assert_ne!(identicon.colour, other.colour);
+
+        // a difference in the last byte changes the pattern, too
+        let identicon = Identicon::new(&[0xb7, 0x52, 0x7e, 0x0e, 0x28], &options).unwrap();
+        let other = Identicon::new(&[0xb7, 0x52, 0x7e, 0x0e, 0x29], &options).unwrap();
+
+        assert_ne!(identicon.cells, other.cells);
+    }
+
+    #[test]
+    fn write_hash_with_options_draws_images() {
+        use super::IdenticonLine;
+        use crate::{ANSIColouredLine, FormatOptions, GraphicsSupport, Line};
+
+        let format = |hash, graphics| {
+            let mut output = String::new();
+            IdenticonLine::write_hash_with_options(
+                hash,
+                &mut output,
+                &FormatOptions {
+                    graphics,
+                    ..FormatOptions::default()
+                },
+            )
+            .unwrap();
+            output
+        };
+        let hash = "b7527e0e28c09f6f62dd2d4197d5d225";
+        let text = ANSIColouredLine::format_hash(hash.to_string());
+
+        let sixel = format(hash, GraphicsSupport::Sixel);
+        assert!(sixel.starts_with("\x1b7\x1bP0;1;0q\"1;1;12;12#1;2;"));
+        assert!(sixel.ends_with(&format!("\x1b\\\x1b8\x1b[3C{}", text)));
+
+        let kitty = format(hash, GraphicsSupport::Kitty);
+        assert!(kitty.starts_with("\x1b_Ga=T,f=32,s=12,v=12,c=2,r=1,q=2;"));
+        assert!(kitty.ends_with(&format!("\x1b\\\x1b[1C{}", text)));
+
+        // digests which can't be decoded are written without an image
+        assert_eq!(format("ASD!", GraphicsSupport::Kitty), "ASD!");
+    }
+}
@@ -11,6 +11,13 @@
 //! with the output from macOS' `md5`, `shasum`, as well as GNU `md5sum`
 //! and `sha256sum`.
 //!
+//! BSD "tag" lines are only recognised when they have the shape
+//! `ALGORITHM (path) = digest`, with a known algorithm name and a digest
+//! of the right length for that algorithm.
+//! GNU-style lines are only recognised when they begin with a plausible
+//! base16 or base64 digest, which may be followed by two spaces, the binary
+//! mode ` *` marker, or a single space, and then a filename.
+//!
 //! They emit their formatted contents when `Display`ed to a user, with
 //! macros like `format!` or `writeln!`:
 //!
@@ -66,15 +73,167 @@
 //!     ")
 //! );
 //! ```
+//!
+//! ## Choosing a formatter at runtime
+//!
+//! `Line` types are chosen at compile time. To choose a formatter at
+//! runtime, use the object-safe `DigestFormatter` trait, and look
+//! formatters up by name from a `FormatterRegistry`. The `coloursum`
+//! function formats a buffer of lines with any `dyn DigestFormatter`.
+//!
+//! ```rust
+//! use coloursum::{coloursum, FormatOptions, FormatterRegistry};
+//!
+//! let registry = FormatterRegistry::default();
+//! let input = "841d462b66e1f4bb839a1b72ab3f3668  ./src/ecoji_line.rs\n".as_bytes();
+//! let mut output_buffer: Vec<u8> = Vec::new();
+//!
+//! coloursum(
+//!     registry.get("ecoji").unwrap(),
+//!     input,
+//!     &mut output_buffer,
+//!     &FormatOptions::default(),
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(
+//!     std::str::from_utf8(&output_buffer).unwrap(),
+//!     "📢💥👛🤓🤴🛌😫🥊🌵🚦😚🚲👱☕☕☕  ./src/ecoji_line.rs\n"
+//! );
+//! ```
+//!
+//! ## Inspecting a line with `ChecksumLine`
+//!
+//! The detection `Line` types use is also available on its own;
+//! `ChecksumLine::parse` breaks a line of checksum output into its style,
+//! algorithm (for BSD "tag" lines), path, digest and mode.
+//!
+//! ```rust
+//! use coloursum::{ChecksumLine, LineStyle};
+//!
+//! let line = ChecksumLine::parse(b"841d462b66e1f4bb839a1b72ab3f3668 *./src/ecoji_line.rs").unwrap();
+//!
+//! assert_eq!(line.style(), LineStyle::Gnu);
+//! assert_eq!(line.algorithm(), None);
+//! assert_eq!(line.path(), b"./src/ecoji_line.rs");
+//! assert_eq!(line.digest(), "841d462b66e1f4bb839a1b72ab3f3668");
+//! assert!(line.is_binary());
+//! ```
+//!
+//! ## Formatting NUL-separated records
+//!
+//! GNU Coreutils' checksum utilities can separate their output with NUL
+//! bytes rather than newlines (`sha256sum --zero`), so that filenames
+//! containing newlines are safe. The `coloursum_with_options` function
+//! accepts a `FormatOptions` value, which can select this record separator.
+//!
+//! ```rust
+//! use coloursum::{EcojiLine, FormatOptions, Line, RecordSeparator};
+//!
+//! let input = "841d462b66e1f4bb839a1b72ab3f3668  ./new\nline\0".as_bytes();
+//! let mut output_buffer: Vec<u8> = Vec::new();
+//!
+//! let options = FormatOptions {
+//!     record_separator: RecordSeparator::Nul,
+//!     ..FormatOptions::default()
+//! };
+//!
+//! EcojiLine::coloursum_with_options(input, &mut output_buffer, &options).unwrap();
+//!
+//! assert_eq!(
+//!     std::str::from_utf8(&output_buffer).unwrap(),
+//!     "📢💥👛🤓🤴🛌😫🥊🌵🚦😚🚲👱☕☕☕  ./new\nline\0"
+//! );
+//! ```
+
+mod abbreviation;
+pub use abbreviation::Abbreviation;
 
 mod base_line;
-pub use base_line::{FormattableLine, Line};
+pub use base_line::{coloursum, FormattableLine, Line};
+
+mod digest_formatter;
+pub use digest_formatter::{DigestFormatter, LineFormatter};
+
+mod formatter_registry;
+pub use formatter_registry::FormatterRegistry;
+
+mod checksum_line;
+pub use checksum_line::{ChecksumLine, LineStyle, Separator};
+
+mod colour;
+
+mod colour_support;
+pub use colour_support::ColourSupport;
+
+mod escape_sequences;
+
+mod format_options;
+pub use format_options::{FormatOptions, RecordSeparator};
+
+mod graphics_support;
+pub use graphics_support::GraphicsSupport;
+
+mod grouping;
+pub use grouping::Grouping;
+
+mod palette;
+pub use palette::Palette;
+
+mod units;
 
 mod ansi_coloured_line;
 pub use ansi_coloured_line::ANSIColouredLine;
 
+mod ansi_block_line;
+pub use ansi_block_line::ANSIBlockLine;
+
+mod base_n_line;
+pub use base_n_line::{
+    Base58Encoding, Base58Line, Base64Encoding, Base64Line, Encoding, NixBase32Encoding,
+    NixBase32Line, ReencodedLine, ZBase32Encoding, ZBase32Line,
+};
+
+mod basic_coloured_line;
+pub use basic_coloured_line::BasicColouredLine;
+
+mod bip39_english;
+
+mod bip39_word_line;
+pub use bip39_word_line::BIP39WordLine;
+
+mod bubble_babble_line;
+pub use bubble_babble_line::BubbleBabbleLine;
+
+mod colour_bar_line;
+pub use colour_bar_line::{ColourBarLine, HexColourBarLine};
+
 mod ecoji_line;
 pub use ecoji_line::EcojiLine;
 
+mod hex_digit_line;
+pub use hex_digit_line::HexDigitLine;
+
+mod identicon_line;
+pub use identicon_line::IdenticonLine;
+
+mod nato_phonetic_line;
+pub use nato_phonetic_line::NATOPhoneticLine;
+
 mod onepassword_line;
 pub use onepassword_line::OnePasswordLine;
+
+mod pgp_word_line;
+pub use pgp_word_line::PGPWordLine;
+
+mod plain_line;
+pub use plain_line::PlainLine;
+
+mod proquint_line;
+pub use proquint_line::ProquintLine;
+
+mod randomart_line;
+pub use randomart_line::RandomartLine;
+
+mod truecolour_line;
+pub use truecolour_line::TruecolourLine;
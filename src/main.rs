@@ -1,26 +1,13 @@
-use clap::{Parser, ValueEnum};
+use clap::builder::PossibleValuesParser;
+use clap::{CommandFactory, FromArgMatches, Parser, ValueEnum};
+use is_terminal::IsTerminal;
 use std::io;
+use std::io::{BufWriter, Write};
 
-use coloursum::{ANSIColouredLine, EcojiLine, Line, OnePasswordLine};
-
-#[derive(Clone, PartialEq, Debug, ValueEnum)]
-enum FormattingMode {
-    ANSIColours,
-    Ecoji,
-    #[value(name = "1password")]
-    OnePassword,
-}
-
-impl ToString for FormattingMode {
-    fn to_string(&self) -> String {
-        match self {
-            FormattingMode::ANSIColours => "ansi-colours",
-            FormattingMode::Ecoji => "ecoji",
-            FormattingMode::OnePassword => "1password",
-        }
-        .to_string()
-    }
-}
+use coloursum::{
+    Abbreviation, ColourSupport, DigestFormatter, FormatOptions, FormatterRegistry,
+    GraphicsSupport, Grouping, Palette, RecordSeparator,
+};
 
 #[derive(Parser, Debug)]
 #[clap(version)]
@@ -35,14 +22,106 @@ struct Options {
 #[derive(Parser, Debug)]
 struct MainOptions {
     /// What sort of formatting to use for checksum values.
+    // possible values are supplied from the `FormatterRegistry` by `parse_options`
+    #[clap(short, long, ignore_case = true, default_value = "ansi-colours")]
+    mode: String,
+
+    /// Treat the input as NUL-separated records, as produced by
+    /// `sha256sum --zero`, rather than lines.
+    #[clap(short, long)]
+    zero: bool,
+
+    /// Show filenames which GNU Coreutils has escaped (lines beginning
+    /// with a backslash) in their original, unescaped form.
+    #[clap(long)]
+    unescape: bool,
+
+    /// Which colours to use, for modes which colour checksum values.
+    #[clap(long, value_enum, ignore_case = true, default_value_t = PaletteOption::Standard)]
+    palette: PaletteOption,
+
+    /// When to colour checksum values.
+    ///
+    /// `auto` colours them only when writing to a terminal, and respects
+    /// the `NO_COLOR`, `CLICOLOR` and `CLICOLOR_FORCE` environment variables.
+    /// Colours are downgraded to those the terminal supports,
+    /// according to `COLORTERM` and `TERM`.
     #[clap(
-        short,
         long,
-        ignore_case = true,
+        visible_alias = "colour",
         value_enum,
-        default_value = "ansi-colours"
+        default_value_t = ColourWhen::Auto
     )]
-    mode: FormattingMode,
+    color: ColourWhen,
+
+    /// Split checksum values into groups of this many characters,
+    /// such as `4` or `8`, to make them easier to read.
+    #[clap(short, long, value_name = "SIZE")]
+    group: Option<usize>,
+
+    /// What to put between groups of characters, when using `--group`.
+    ///
+    /// For example, `--group 2 --group-separator :` writes colon-separated
+    /// bytes, like X.509 certificate fingerprints.
+    #[clap(
+        long,
+        value_name = "SEPARATOR",
+        default_value = " ",
+        requires = "group"
+    )]
+    group_separator: String,
+
+    /// Show only the start and end of checksum values, like `3e08ba70…684bda`.
+    ///
+    /// Takes how many characters to show from the start and end, such as
+    /// `4` or `8,6`, defaulting to `8,6`. The characters left out are
+    /// still used to colour the ellipsis, so checksum values which differ
+    /// only in the middle usually look different; the fewer colours are
+    /// available, the more often two share an ellipsis colour.
+    #[clap(
+        long,
+        value_name = "HEAD[,TAIL]",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "8,6"
+    )]
+    abbrev: Option<Abbreviation>,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, ValueEnum)]
+enum ColourWhen {
+    /// Colour output only when writing to a terminal
+    Auto,
+    /// Always colour output
+    Always,
+    /// Never colour output
+    Never,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, ValueEnum)]
+enum PaletteOption {
+    /// The mode's usual colours
+    Standard,
+    /// Blues and oranges, for red-green colour blindness (missing green cones)
+    Deuteranopia,
+    /// Blues and yellows, for red-green colour blindness (missing red cones)
+    Protanopia,
+    /// Reds and teals, for blue-yellow colour blindness
+    Tritanopia,
+    /// Shades of grey, using differences in lightness alone
+    Greyscale,
+}
+
+impl From<PaletteOption> for Palette {
+    fn from(option: PaletteOption) -> Self {
+        match option {
+            PaletteOption::Standard => Palette::Standard,
+            PaletteOption::Deuteranopia => Palette::Deuteranopia,
+            PaletteOption::Protanopia => Palette::Protanopia,
+            PaletteOption::Tritanopia => Palette::Tritanopia,
+            PaletteOption::Greyscale => Palette::Greyscale,
+        }
+    }
 }
 
 #[derive(Parser, Debug)]
@@ -82,17 +161,86 @@ struct ShellSetupOptions {
     command: Option<String>,
 }
 
-fn coloursum(options: &MainOptions) -> io::Result<()> {
+/// Whether the environment variable `name` is set to something
+/// other than an empty string or `0`.
+fn env_flag(name: &str) -> bool {
+    matches!(std::env::var_os(name), Some(value) if !value.is_empty() && value != "0")
+}
+
+/// Works out which colours to use, following the `NO_COLOR`
+/// (<https://no-color.org>) and `CLICOLOR`/`CLICOLOR_FORCE`
+/// (<https://bixense.com/clicolors/>) conventions.
+fn colour_support(when: ColourWhen, stdout_is_terminal: bool) -> ColourSupport {
+    let forced = match when {
+        ColourWhen::Never => return ColourSupport::None,
+        ColourWhen::Always => true,
+        ColourWhen::Auto => {
+            // `NO_COLOR` disables colour whatever its value, unless it's empty
+            if matches!(std::env::var_os("NO_COLOR"), Some(value) if !value.is_empty()) {
+                return ColourSupport::None;
+            }
+
+            env_flag("CLICOLOR_FORCE")
+        }
+    };
+
+    if forced {
+        // colours were asked for, so even if the terminal
+        // claims not to support any, use the basic ones
+        ColourSupport::detect().max(ColourSupport::Basic)
+    } else if !stdout_is_terminal
+        || matches!(std::env::var_os("CLICOLOR"), Some(value) if value == "0")
+    {
+        ColourSupport::None
+    } else {
+        ColourSupport::detect()
+    }
+}
+
+fn coloursum(formatter: &dyn DigestFormatter, options: &MainOptions) -> io::Result<()> {
     let stdin = io::stdin();
     let locked_stdin = stdin.lock();
 
     let stdout = io::stdout();
     let locked_stdout = stdout.lock();
 
-    match options.mode {
-        FormattingMode::ANSIColours => ANSIColouredLine::coloursum(locked_stdin, locked_stdout),
-        FormattingMode::Ecoji => EcojiLine::coloursum(locked_stdin, locked_stdout),
-        FormattingMode::OnePassword => OnePasswordLine::coloursum(locked_stdin, locked_stdout),
+    let colour_support = colour_support(options.color, stdout.is_terminal());
+
+    let format_options = FormatOptions {
+        record_separator: if options.zero {
+            RecordSeparator::Nul
+        } else {
+            RecordSeparator::Newline
+        },
+        unescape_filenames: options.unescape,
+        palette: options.palette.into(),
+        colour_support,
+        // images are only drawn when they'll be seen, and alongside colours
+        graphics: if stdout.is_terminal() && colour_support != ColourSupport::None {
+            GraphicsSupport::detect()
+        } else {
+            GraphicsSupport::None
+        },
+        grouping: options
+            .group
+            .map(|size| Grouping::new(size, options.group_separator.clone())),
+        abbreviation: options.abbrev,
+    };
+
+    if stdout.is_terminal() {
+        // stdout is line buffered, so people see each line as soon as it's ready
+        coloursum::coloursum(formatter, locked_stdin, locked_stdout, &format_options)
+    } else {
+        // but when writing to a file or pipe, buffer output to avoid
+        // making a write call for every line
+        let mut buffered_stdout = BufWriter::new(locked_stdout);
+        coloursum::coloursum(
+            formatter,
+            locked_stdin,
+            &mut buffered_stdout,
+            &format_options,
+        )?;
+        buffered_stdout.flush()
     }
 }
 
@@ -167,36 +315,146 @@ fn print_shell_function(options: &MainOptions, shell_name: &str, command: String
         Err(_) => "coloursum".to_string(),
     };
 
+    println!(
+        "{}",
+        shell_function(options, shell_name, &command, &exe_name)
+    );
+}
+
+/// Writes a function for `shell_name` which pipes `command`'s output
+/// through coloursum, run as `exe_name` with the same options as this one.
+#[cfg(unix)]
+fn shell_function(
+    options: &MainOptions,
+    shell_name: &str,
+    command: &str,
+    exe_name: &str,
+) -> String {
+    let coloursum = std::iter::once(exe_name.to_string())
+        .chain(forwarded_arguments(options))
+        .map(|argument| quote_for_shell(shell_name, &argument))
+        .collect::<Vec<_>>()
+        .join(" ");
+
     match shell_name {
-        "fish" => println!(
+        "fish" => format!(
             "function {0}\n\
-            \tcommand {0} $argv | {1} --mode {2}\n\
+            \tcommand {0} $argv | {1}\n\
             end",
-            command,
-            exe_name,
-            options.mode.to_string()
+            command, coloursum
         ),
-        "ksh" => println!(
+        "ksh" => format!(
             "function {0} {{\n\
-            \tcommand {0} \"$@\" | {1} --mode {2}\n\
+            \tcommand {0} \"$@\" | {1}\n\
             }}",
-            command,
-            exe_name,
-            options.mode.to_string()
+            command, coloursum
         ),
-        _ => println!(
+        _ => format!(
             "function {0}() {{\n\
-            \tcommand {0} \"$@\" | {1} --mode {2}\n\
+            \tcommand {0} \"$@\" | {1}\n\
             }}",
-            command,
-            exe_name,
-            options.mode.to_string()
+            command, coloursum
         ),
     }
 }
 
+/// Lists the command line arguments which give coloursum the same
+/// options as `options`, leaving out any which are set to their defaults.
+#[cfg(unix)]
+fn forwarded_arguments(options: &MainOptions) -> Vec<String> {
+    let mut arguments = vec!["--mode".to_string(), options.mode.clone()];
+
+    if options.zero {
+        arguments.push("--zero".to_string());
+    }
+
+    if options.unescape {
+        arguments.push("--unescape".to_string());
+    }
+
+    if options.palette != PaletteOption::Standard {
+        if let Some(value) = options.palette.to_possible_value() {
+            arguments.push("--palette".to_string());
+            arguments.push(value.get_name().to_string());
+        }
+    }
+
+    if options.color != ColourWhen::Auto {
+        if let Some(value) = options.color.to_possible_value() {
+            arguments.push("--color".to_string());
+            arguments.push(value.get_name().to_string());
+        }
+    }
+
+    if let Some(size) = options.group {
+        arguments.push("--group".to_string());
+        arguments.push(size.to_string());
+        // the separator can only be given alongside a group size
+        if options.group_separator != " " {
+            arguments.push("--group-separator".to_string());
+            arguments.push(options.group_separator.clone());
+        }
+    }
+
+    if let Some(abbreviation) = options.abbrev {
+        arguments.push(format!(
+            "--abbrev={},{}",
+            abbreviation.head, abbreviation.tail
+        ));
+    }
+
+    arguments
+}
+
+/// Quotes `argument` so that `shell_name` passes it on unchanged.
+///
+/// Arguments which the shell wouldn't change are left as they are,
+/// to keep the generated functions readable.
+#[cfg(unix)]
+fn quote_for_shell(shell_name: &str, argument: &str) -> String {
+    let is_plain = !argument.is_empty()
+        && argument
+            .chars()
+            .all(|character| character.is_ascii_alphanumeric() || "-_./,=:+@".contains(character));
+
+    if is_plain {
+        argument.to_string()
+    } else if shell_name == "fish" {
+        // fish allows escaping backslashes and quotes within single quotes
+        format!("'{}'", argument.replace('\\', "\\\\").replace('\'', "\\'"))
+    } else {
+        // other shells don't, so close the quotes, and add an escaped quote
+        format!("'{}'", argument.replace('\'', "'\\''"))
+    }
+}
+
+/// Parses the command line options, accepting the names
+/// of the formatters in `registry` as modes.
+fn parse_options(registry: &FormatterRegistry) -> Options {
+    let names: Vec<String> = registry.names().map(str::to_string).collect();
+
+    let matches = Options::command()
+        .mut_arg("mode", |arg| {
+            arg.value_parser(PossibleValuesParser::new(names.clone()))
+        })
+        .get_matches();
+
+    let mut options = Options::from_arg_matches(&matches).unwrap_or_else(|error| error.exit());
+
+    // modes are matched case-insensitively, so use the registered spelling
+    if let Some(name) = names
+        .into_iter()
+        .find(|name| name.eq_ignore_ascii_case(&options.main_options.mode))
+    {
+        options.main_options.mode = name;
+    }
+
+    options
+}
+
 fn main() -> Result<(), std::io::Error> {
-    let options = Options::parse();
+    let registry = FormatterRegistry::default();
+    let options = parse_options(&registry);
 
     if let Some(command) = options.cmd {
         match command {
@@ -206,6 +464,117 @@ fn main() -> Result<(), std::io::Error> {
             }
         }
     } else {
-        coloursum(&options.main_options)
+        let formatter = registry
+            .get(&options.main_options.mode)
+            .expect("the mode should be validated against the registry");
+
+        coloursum(formatter, &options.main_options)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    #[test]
+    fn shell_function_works() {
+        use super::{shell_function, MainOptions};
+        use clap::Parser;
+
+        let options = MainOptions::parse_from(["coloursum", "--mode", "ecoji"]);
+
+        assert_eq!(
+            shell_function(&options, "bash", "md5sum", "/usr/bin/coloursum"),
+            "function md5sum() {\n\
+             \tcommand md5sum \"$@\" | /usr/bin/coloursum --mode ecoji\n\
+             }"
+        );
+        assert_eq!(
+            shell_function(&options, "ksh", "md5sum", "/usr/bin/coloursum"),
+            "function md5sum {\n\
+             \tcommand md5sum \"$@\" | /usr/bin/coloursum --mode ecoji\n\
+             }"
+        );
+        assert_eq!(
+            shell_function(&options, "fish", "md5sum", "/usr/bin/coloursum"),
+            "function md5sum\n\
+             \tcommand md5sum $argv | /usr/bin/coloursum --mode ecoji\n\
+             end"
+        );
+    }
+
+    #[test]
+    fn shell_function_forwards_every_option() {
+        use super::{shell_function, MainOptions};
+        use clap::Parser;
+
+        let options = MainOptions::parse_from([
+            "coloursum",
+            "--zero",
+            "--unescape",
+            "--palette",
+            "greyscale",
+            "--colour",
+            "always",
+            "--group",
+            "4",
+            "--group-separator",
+            " / ",
+            "--abbrev=12,4",
+        ]);
+
+        assert_eq!(
+            shell_function(&options, "zsh", "sha256sum", "coloursum"),
+            "function sha256sum() {\n\
+             \tcommand sha256sum \"$@\" | coloursum --mode ansi-colours --zero --unescape \
+             --palette greyscale --color always --group 4 --group-separator ' / ' --abbrev=12,4\n\
+             }"
+        );
+    }
+
+    #[test]
+    fn shell_function_quotes_arguments() {
+        use super::{shell_function, MainOptions};
+        use clap::Parser;
+
+        let options =
+            MainOptions::parse_from(["coloursum", "--group", "2", "--group-separator", "it's\\"]);
+
+        assert_eq!(
+            shell_function(
+                &options,
+                "bash",
+                "md5",
+                "/Applications/Colour Sum/coloursum"
+            ),
+            "function md5() {\n\
+             \tcommand md5 \"$@\" | '/Applications/Colour Sum/coloursum' --mode ansi-colours \
+             --group 2 --group-separator 'it'\\''s\\'\n\
+             }"
+        );
+        assert_eq!(
+            shell_function(
+                &options,
+                "fish",
+                "md5",
+                "/Applications/Colour Sum/coloursum"
+            ),
+            "function md5\n\
+             \tcommand md5 $argv | '/Applications/Colour Sum/coloursum' --mode ansi-colours \
+             --group 2 --group-separator 'it\\'s\\\\'\n\
+             end"
+        );
+    }
+
+    #[test]
+    fn quote_for_shell_works() {
+        use super::quote_for_shell;
+
+        assert_eq!(quote_for_shell("bash", "--abbrev=8,6"), "--abbrev=8,6");
+        assert_eq!(quote_for_shell("bash", ""), "''");
+        assert_eq!(quote_for_shell("bash", "Colour Sum"), "'Colour Sum'");
+        assert_eq!(quote_for_shell("zsh", "it's"), "'it'\\''s'");
+        assert_eq!(quote_for_shell("bash", "\"$HOME\""), "'\"$HOME\"'");
+        assert_eq!(quote_for_shell("fish", "Colour Sum"), "'Colour Sum'");
+        assert_eq!(quote_for_shell("fish", "it's"), "'it\\'s'");
+        assert_eq!(quote_for_shell("fish", "back\\slash"), "'back\\\\slash'");
     }
 }
@@ -0,0 +1,122 @@
+use std::convert::From;
+use std::fmt;
+use std::fmt::Display;
+
+use crate::base_line::{format_to_string, FormattableLine, Line};
+use crate::format_options::FormatOptions;
+use crate::units::write_units;
+
+/// The spoken word for each hexadecimal digit, from `0` to `f`,
+/// using the NATO/ICAO phonetic alphabet for the letters.
+const DIGIT_WORDS: [&str; 16] = [
+    "Zero", "One", "Two", "Three", "Four", "Five", "Six", "Seven", "Eight", "Nine", "Alfa",
+    "Bravo", "Charlie", "Delta", "Echo", "Foxtrot",
+];
+
+#[derive(Debug)]
+/// Line with each hexadecimal digit spelled out in the NATO phonetic
+/// alphabet, for reading digests aloud over the radio or phone.
+pub struct NATOPhoneticLine(FormattableLine);
+impl From<String> for NATOPhoneticLine {
+    fn from(contents: String) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl From<Vec<u8>> for NATOPhoneticLine {
+    fn from(contents: Vec<u8>) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl Display for NATOPhoneticLine {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.to_formatted(formatter)
+    }
+}
+
+impl Line for NATOPhoneticLine {
+    fn get_line(&self) -> &FormattableLine {
+        &self.0
+    }
+
+    fn format_hash(hash: String) -> String {
+        format_to_string(&hash, Self::write_hash)
+    }
+
+    /// Formats a base16-format hash or digest.
+    ///
+    /// Each digit will be written as its spoken word, such as
+    /// `Bravo Seven Five Two`, separated by spaces.
+    fn write_hash(hash: &str, to: &mut dyn fmt::Write) -> fmt::Result {
+        Self::write_hash_with_options(hash, to, &FormatOptions::default())
+    }
+
+    /// Formats a base16-format hash or digest.
+    ///
+    /// Each digit will be written as its spoken word, separated by spaces.
+    /// If `options.grouping` is set, its separator is written, surrounded
+    /// by spaces unless it's whitespace itself, after each group of that
+    /// many digits, such as `Bravo Seven / Five Two` for groups of 2
+    /// separated by `/`.
+    fn write_hash_with_options(
+        hash: &str,
+        to: &mut dyn fmt::Write,
+        options: &FormatOptions,
+    ) -> fmt::Result {
+        let words: Option<Vec<&str>> = hash
+            .chars()
+            .map(|character| Some(DIGIT_WORDS[character.to_digit(16)? as usize]))
+            .collect();
+
+        match words {
+            Some(words) => write_units(hash, &words, " ", to, options),
+            // if there are any non-base16 characters, write the original value
+            None => to.write_str(hash),
+        }
+    }
+
+    fn writes_units() -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn display_works() {
+        use super::NATOPhoneticLine;
+
+        assert_eq!(
+            format!(
+                "{}",
+                NATOPhoneticLine::from(
+                    "MD5 (./src/main.rs) = b7527e0e28c09f6f62dd2d4197d5d225".to_string()
+                )
+            ),
+            "MD5 (./src/main.rs) = Bravo Seven Five Two Seven Echo Zero Echo Two Eight Charlie \
+             Zero Nine Foxtrot Six Foxtrot Six Two Delta Delta Two Delta Four One Nine Seven \
+             Delta Five Delta Two Two Five"
+        )
+    }
+
+    #[test]
+    fn format_hash_works() {
+        use super::NATOPhoneticLine;
+        use crate::Line;
+
+        assert_eq!(
+            NATOPhoneticLine::format_hash("0123456789ABCDEF".to_string()),
+            "Zero One Two Three Four Five Six Seven Eight Nine Alfa Bravo Charlie Delta Echo Foxtrot"
+        );
+    }
+
+    #[test]
+    fn format_hash_doesnt_crash_on_non_base16_characters() {
+        use super::NATOPhoneticLine;
+        use crate::Line;
+
+        assert_eq!(NATOPhoneticLine::format_hash("ASDF".to_string()), "ASDF");
+        assert_eq!(NATOPhoneticLine::format_hash("😄".to_string()), "😄");
+    }
+}
@@ -2,9 +2,9 @@ use std::convert::From;
 use std::fmt;
 use std::fmt::Display;
 
-use ansi_term::Colour::Fixed;
-
-use crate::base_line::{FormattableLine, Line};
+use crate::base_line::{format_to_string, FormattableLine, Line};
+use crate::colour::{write_painted, TermColour};
+use crate::format_options::FormatOptions;
 
 #[derive(Debug)]
 /// Line with formatting which colours numeric digits in blue,
@@ -16,6 +16,12 @@ impl From<String> for OnePasswordLine {
     }
 }
 
+impl From<Vec<u8>> for OnePasswordLine {
+    fn from(contents: Vec<u8>) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
 impl Display for OnePasswordLine {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         self.to_formatted(formatter)
@@ -27,19 +33,42 @@ impl Line for OnePasswordLine {
         &self.0
     }
 
+    fn format_hash(hash: String) -> String {
+        format_to_string(&hash, Self::write_hash)
+    }
+
     /// Formats a base16-format hash or digest.
     ///
     /// Any numeric characters are formatted in blue.
-    fn format_hash(hash: String) -> String {
-        hash.chars()
-            .map(|character| {
-                if character.is_ascii_digit() {
-                    Fixed(4).paint(character.to_string()).to_string()
-                } else {
-                    character.to_string()
-                }
-            })
-            .collect()
+    fn write_hash(hash: &str, to: &mut dyn fmt::Write) -> fmt::Result {
+        Self::write_hash_with_options(hash, to, &FormatOptions::default())
+    }
+
+    /// Formats a base16-format hash or digest.
+    ///
+    /// Any numeric characters are formatted in blue, if
+    /// `options.colour_support` allows colours at all.
+    fn write_hash_with_options(
+        hash: &str,
+        to: &mut dyn fmt::Write,
+        options: &FormatOptions,
+    ) -> fmt::Result {
+        for (offset, character) in hash.char_indices() {
+            let character_string = &hash[offset..offset + character.len_utf8()];
+
+            if character.is_ascii_digit() {
+                write_painted(
+                    to,
+                    TermColour::Fixed(4),
+                    character_string,
+                    options.colour_support,
+                )?;
+            } else {
+                to.write_str(character_string)?;
+            }
+        }
+
+        Ok(())
     }
 }
 
@@ -76,4 +105,25 @@ mod tests {
         OnePasswordLine::format_hash("ASDF".to_string());
         OnePasswordLine::format_hash("😄".to_string());
     }
+
+    #[test]
+    fn coloursum_preserves_line_endings() {
+        use super::OnePasswordLine;
+        use crate::Line;
+
+        let input: &[u8] = b"MD5 (./src/main.rs) = b7527e0e28c09f6f62dd2d4197d5d225\r\nb7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs\nb7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs";
+        let mut output: Vec<u8> = Vec::new();
+
+        OnePasswordLine::coloursum(input, &mut output).unwrap();
+
+        let hash = OnePasswordLine::format_hash("b7527e0e28c09f6f62dd2d4197d5d225".to_string());
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            format!(
+                "MD5 (./src/main.rs) = {0}\r\n{0}  ./src/main.rs\n{0}  ./src/main.rs",
+                hash
+            )
+        );
+    }
 }
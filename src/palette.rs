@@ -0,0 +1,423 @@
+use crate::colour::Oklch;
+
+/// The colour of each hexadecimal digit, from `0` to `f`, for
+/// `Palette::Deuteranopia`.
+///
+/// Like the byte colours, these are a grid of four lightnesses by four
+/// points between the palette's two hues; each digit is seven cells on
+/// from the one before, so consecutive digits have unrelated colours.
+const DEUTERANOPIA_DIGIT_COLOURS: [(u8, u8, u8); 16] = [
+    (34, 102, 164),  // 0: oklch(0.50 0.12 250)
+    (177, 111, 35),  // 1: oklch(0.60 0.12 65)
+    (208, 185, 163), // 2: oklch(0.80 0.04 65)
+    (110, 131, 152), // 3: oklch(0.60 0.04 250)
+    (137, 195, 255), // 4: oklch(0.80 0.12 250)
+    (142, 83, 0),    // 5: oklch(0.50 0.12 65)
+    (176, 154, 133), // 6: oklch(0.70 0.04 65)
+    (82, 101, 122),  // 7: oklch(0.50 0.04 250)
+    (97, 163, 230),  // 8: oklch(0.70 0.12 250)
+    (243, 173, 102), // 9: oklch(0.80 0.12 65)
+    (145, 124, 103), // a: oklch(0.60 0.04 65)
+    (171, 192, 215), // b: oklch(0.80 0.04 250)
+    (66, 132, 197),  // c: oklch(0.60 0.12 250)
+    (209, 142, 70),  // d: oklch(0.70 0.12 65)
+    (116, 95, 75),   // e: oklch(0.50 0.04 65)
+    (140, 161, 183), // f: oklch(0.70 0.04 250)
+];
+
+/// The colour of each hexadecimal digit for `Palette::Protanopia`,
+/// laid out like `DEUTERANOPIA_DIGIT_COLOURS`.
+const PROTANOPIA_DIGIT_COLOURS: [(u8, u8, u8); 16] = [
+    (59, 94, 178),   // 0: oklch(0.50 0.14 265)
+    (146, 129, 0),   // 1: oklch(0.60 0.14 100)
+    (194, 191, 169), // 2: oklch(0.80 0.03 100)
+    (119, 128, 147), // 3: oklch(0.60 0.03 265)
+    (158, 189, 255), // 4: oklch(0.80 0.14 265)
+    (114, 100, 0),   // 5: oklch(0.50 0.14 100)
+    (162, 159, 138), // 6: oklch(0.70 0.03 100)
+    (91, 99, 117),   // 7: oklch(0.50 0.03 265)
+    (115, 155, 245), // 8: oklch(0.70 0.14 265)
+    (211, 191, 70),  // 9: oklch(0.80 0.14 100)
+    (132, 129, 109), // a: oklch(0.60 0.03 100)
+    (180, 190, 210), // b: oklch(0.80 0.03 265)
+    (86, 124, 211),  // c: oklch(0.60 0.14 265)
+    (179, 159, 24),  // d: oklch(0.70 0.14 100)
+    (103, 100, 80),  // e: oklch(0.50 0.03 100)
+    (149, 159, 178), // f: oklch(0.70 0.03 265)
+];
+
+/// The colour of each hexadecimal digit for `Palette::Tritanopia`,
+/// laid out like `DEUTERANOPIA_DIGIT_COLOURS`.
+const TRITANOPIA_DIGIT_COLOURS: [(u8, u8, u8); 16] = [
+    (164, 58, 65),   // 0: oklch(0.50 0.14 20)
+    (0, 147, 147),   // 1: oklch(0.60 0.14 195)
+    (168, 196, 196), // 2: oklch(0.80 0.03 195)
+    (146, 121, 121), // 3: oklch(0.60 0.03 20)
+    (255, 159, 159), // 4: oklch(0.80 0.14 20)
+    (0, 114, 114),   // 5: oklch(0.50 0.14 195)
+    (138, 165, 164), // 6: oklch(0.70 0.03 195)
+    (116, 93, 92),   // 7: oklch(0.50 0.03 20)
+    (232, 119, 122), // 8: oklch(0.70 0.14 20)
+    (0, 216, 217),   // 9: oklch(0.80 0.14 195)
+    (108, 134, 134), // a: oklch(0.60 0.03 195)
+    (209, 183, 182), // b: oklch(0.80 0.03 20)
+    (198, 88, 93),   // c: oklch(0.60 0.14 20)
+    (0, 181, 181),   // d: oklch(0.70 0.14 195)
+    (79, 105, 104),  // e: oklch(0.50 0.03 195)
+    (177, 151, 151), // f: oklch(0.70 0.03 20)
+];
+
+/// The colour of each hexadecimal digit for `Palette::Greyscale`.
+///
+/// These are greys which the xterm 256 colour palette has too, so they
+/// stay distinct when colours are downgraded, with each digit seven
+/// steps lighter than the one before, wrapping around at the end.
+const GREYSCALE_DIGIT_COLOURS: [(u8, u8, u8); 16] = [
+    (68, 68, 68),
+    (138, 138, 138),
+    (228, 228, 228),
+    (118, 118, 118),
+    (198, 198, 198),
+    (98, 98, 98),
+    (168, 168, 168),
+    (78, 78, 78),
+    (148, 148, 148),
+    (238, 238, 238),
+    (128, 128, 128),
+    (215, 215, 215),
+    (108, 108, 108),
+    (178, 178, 178),
+    (88, 88, 88),
+    (158, 158, 158),
+];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// Which colours the colouring formatters use for each byte of a digest.
+///
+/// The palettes other than `Standard` are designed for people with colour
+/// vision deficiencies; each uses only differences in colour which people
+/// with that deficiency can see, along with differences in lightness, such
+/// that every byte gets a colour of its own, and bytes with nearby values
+/// (like `4a` and `4b`) look distinct.
+///
+/// Every byte only gets its own colour with 24-bit colour, as in
+/// `TruecolourLine`. Formatters which use the xterm 256 colour palette, like
+/// `ANSIColouredLine`, and any colours downgraded to it, use the closest
+/// colour there, leaving only around 30 distinct colours for each palette;
+/// bytes within 3 of each other (2 for `Greyscale`) still never share one.
+pub enum Palette {
+    /// Each formatter's usual colours.
+    #[default]
+    Standard,
+    /// For red-green colour blindness caused by missing green cones;
+    /// uses blues and oranges.
+    Deuteranopia,
+    /// For red-green colour blindness caused by missing red cones;
+    /// uses blues and yellows, avoiding reds, which appear dark.
+    Protanopia,
+    /// For blue-yellow colour blindness; uses reds and teals.
+    Tritanopia,
+    /// Uses differences in lightness alone.
+    ///
+    /// There are fewer greys than bytes, so bytes with very different
+    /// values can share a grey; bytes within 64 of each other never do.
+    Greyscale,
+}
+
+impl Palette {
+    /// Picks the colour for a byte of a digest.
+    ///
+    /// Returns `None` for `Palette::Standard`,
+    /// where each formatter picks its own colours.
+    pub(crate) fn colour(self, byte: u8) -> Option<Oklch> {
+        let hues = match self {
+            Palette::Standard => return None,
+            Palette::Deuteranopia => [250.0, 65.0],
+            Palette::Protanopia => [265.0, 100.0],
+            Palette::Tritanopia => [20.0, 195.0],
+            Palette::Greyscale => {
+                // each step between neighbouring values jumps about a third
+                // of the way across the range, wrapping around at the end,
+                // so neighbours always differ
+                let position = f32::from(byte.wrapping_mul(81)) / 255.0;
+
+                return Some(Oklch {
+                    lightness: 0.4 + 0.55 * position,
+                    chroma: 0.0,
+                    hue: 0.0,
+                });
+            }
+        };
+
+        // scatter the bytes across a 16 by 16 grid, so that nearby values
+        // land far apart; as 133 is odd, no two bytes share a position
+        let position = byte.wrapping_mul(133);
+        let (row, column) = (f32::from(position >> 4), f32::from(position & 15));
+
+        // rows are lightnesses, and columns are points along the line from
+        // one hue, through grey, to the other; both stay within sRGB's gamut,
+        // so no two colours are squashed together to fit
+        let lightness = 0.5 + 0.35 * row / 15.0;
+        let axis = 0.07 * (column / 7.5 - 1.0);
+
+        Some(Oklch {
+            lightness,
+            chroma: axis.abs(),
+            hue: if axis < 0.0 { hues[0] } else { hues[1] },
+        })
+    }
+
+    /// Picks the colour for a hexadecimal digit, for formatters which
+    /// colour each digit of a digest on its own.
+    ///
+    /// There are only 16 digits, so these are further apart than the
+    /// colours for bytes. Returns `None` for `Palette::Standard`,
+    /// where each formatter picks its own colours.
+    pub(crate) fn digit_colour(self, digit: u8) -> Option<(u8, u8, u8)> {
+        let colours = match self {
+            Palette::Standard => return None,
+            Palette::Deuteranopia => &DEUTERANOPIA_DIGIT_COLOURS,
+            Palette::Protanopia => &PROTANOPIA_DIGIT_COLOURS,
+            Palette::Tritanopia => &TRITANOPIA_DIGIT_COLOURS,
+            Palette::Greyscale => &GREYSCALE_DIGIT_COLOURS,
+        };
+
+        colours.get(usize::from(digit)).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    /// Simulates how a colour looks to someone with a colour vision
+    /// deficiency, and converts the result to OKLab.
+    ///
+    /// `deficiency` is one of Machado et al's (2009) matrices, for linear RGB.
+    fn simulate((red, green, blue): (u8, u8, u8), deficiency: [[f32; 3]; 3]) -> [f32; 3] {
+        fn decode(channel: u8) -> f32 {
+            let channel = f32::from(channel) / 255.0;
+
+            if channel <= 0.040_45 {
+                channel / 12.92
+            } else {
+                ((channel + 0.055) / 1.055).powf(2.4)
+            }
+        }
+
+        let linear = [decode(red), decode(green), decode(blue)];
+        let [red, green, blue] = deficiency.map(|row| {
+            (row[0] * linear[0] + row[1] * linear[1] + row[2] * linear[2]).clamp(0.0, 1.0)
+        });
+
+        let l = (0.412_221_46 * red + 0.536_332_55 * green + 0.051_445_995 * blue).cbrt();
+        let m = (0.211_903_5 * red + 0.680_699_5 * green + 0.107_396_96 * blue).cbrt();
+        let s = (0.088_302_46 * red + 0.281_718_85 * green + 0.629_978_7 * blue).cbrt();
+
+        [
+            0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+            1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+            0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+        ]
+    }
+
+    const NORMAL: [[f32; 3]; 3] = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    const DEUTERANOPIA: [[f32; 3]; 3] = [
+        [0.367_322, 0.860_646, -0.227_968],
+        [0.280_085, 0.672_501, 0.047_413],
+        [-0.011_820, 0.042_940, 0.968_881],
+    ];
+    const PROTANOPIA: [[f32; 3]; 3] = [
+        [0.152_286, 1.052_583, -0.204_868],
+        [0.114_503, 0.786_281, 0.099_216],
+        [-0.003_882, -0.048_116, 1.051_998],
+    ];
+    const TRITANOPIA: [[f32; 3]; 3] = [
+        [1.255_528, -0.076_749, -0.178_779],
+        [-0.078_411, 0.930_809, 0.147_602],
+        [0.004_733, 0.691_367, 0.303_900],
+    ];
+
+    #[test]
+    fn standard_has_no_colours() {
+        use super::Palette;
+
+        assert_eq!(Palette::Standard.colour(0x4a), None);
+        assert_eq!(Palette::Standard.digit_colour(0xa), None);
+    }
+
+    #[test]
+    fn every_byte_gets_its_own_colour() {
+        use super::Palette;
+        use std::collections::HashSet;
+
+        for palette in [
+            Palette::Deuteranopia,
+            Palette::Protanopia,
+            Palette::Tritanopia,
+        ] {
+            let colours: HashSet<_> = (0..=u8::MAX)
+                .map(|byte| palette.colour(byte).unwrap().to_rgb())
+                .collect();
+
+            assert_eq!(colours.len(), 256, "{:?} reuses colours", palette);
+        }
+
+        // there aren't enough greys to go around, but nearby bytes never share
+        for byte in 0..=u8::MAX {
+            for other in (1..=64).filter_map(|distance| byte.checked_add(distance)) {
+                assert_ne!(
+                    Palette::Greyscale.colour(byte).unwrap().to_rgb(),
+                    Palette::Greyscale.colour(other).unwrap().to_rgb(),
+                    "{:02x} and {:02x} share a grey",
+                    byte,
+                    other
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn nearby_bytes_keep_distinct_xterm_colours() {
+        use super::Palette;
+        use crate::colour::xterm_colour;
+
+        for (palette, distances) in [
+            (Palette::Deuteranopia, 1..=3),
+            (Palette::Protanopia, 1..=3),
+            (Palette::Tritanopia, 1..=3),
+            (Palette::Greyscale, 1..=2),
+        ] {
+            let xterm = |byte| xterm_colour(palette.colour(byte).unwrap().to_rgb()).0;
+
+            for byte in 0..=u8::MAX {
+                for other in distances
+                    .clone()
+                    .filter_map(|distance| byte.checked_add(distance))
+                {
+                    assert_ne!(
+                        xterm(byte),
+                        xterm(other),
+                        "{:?}: {:02x} and {:02x} share an xterm colour",
+                        palette,
+                        byte,
+                        other
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn nearby_bytes_look_distinct_to_their_audience() {
+        use super::Palette;
+
+        for (palette, deficiency) in [
+            (Palette::Deuteranopia, DEUTERANOPIA),
+            (Palette::Protanopia, PROTANOPIA),
+            (Palette::Tritanopia, TRITANOPIA),
+            (Palette::Greyscale, NORMAL),
+            (Palette::Greyscale, DEUTERANOPIA),
+        ] {
+            for byte in 0..=u8::MAX {
+                for other in (2..=16).filter_map(|distance| byte.checked_add(distance)) {
+                    let [l1, a1, b1] = simulate(palette.colour(byte).unwrap().to_rgb(), deficiency);
+                    let [l2, a2, b2] =
+                        simulate(palette.colour(other).unwrap().to_rgb(), deficiency);
+
+                    let distance =
+                        ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt();
+
+                    // around the smallest difference people can notice
+                    assert!(
+                        distance > 0.02,
+                        "{:?}: {:02x} and {:02x} are too similar",
+                        palette,
+                        byte,
+                        other
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn adjacent_bytes_look_distinct_to_their_audience() {
+        use super::Palette;
+        use crate::colour::xterm_colour;
+
+        for (palette, deficiency) in [
+            (Palette::Deuteranopia, DEUTERANOPIA),
+            (Palette::Protanopia, PROTANOPIA),
+            (Palette::Tritanopia, TRITANOPIA),
+            (Palette::Greyscale, NORMAL),
+            (Palette::Greyscale, DEUTERANOPIA),
+        ] {
+            for byte in 0..u8::MAX {
+                let first = palette.colour(byte).unwrap().to_rgb();
+                let second = palette.colour(byte + 1).unwrap().to_rgb();
+
+                // check both the 24-bit colours, and their closest xterm colours
+                for (first, second) in [
+                    (first, second),
+                    (xterm_colour(first).1, xterm_colour(second).1),
+                ] {
+                    let [l1, a1, b1] = simulate(first, deficiency);
+                    let [l2, a2, b2] = simulate(second, deficiency);
+
+                    let distance =
+                        ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt();
+
+                    assert!(
+                        distance > 0.08,
+                        "{:?}: {:02x} and {:02x} are too similar",
+                        palette,
+                        byte,
+                        byte + 1
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn digits_look_distinct_to_their_audience() {
+        use super::Palette;
+        use crate::colour::xterm_colour;
+
+        for (palette, deficiency) in [
+            (Palette::Deuteranopia, DEUTERANOPIA),
+            (Palette::Protanopia, PROTANOPIA),
+            (Palette::Tritanopia, TRITANOPIA),
+            (Palette::Greyscale, NORMAL),
+            (Palette::Greyscale, DEUTERANOPIA),
+        ] {
+            for digit in 0..16 {
+                for other in digit + 1..16 {
+                    let first = palette.digit_colour(digit).unwrap();
+                    let second = palette.digit_colour(other).unwrap();
+
+                    // check both the 24-bit colours, and their closest xterm colours
+                    for (first, second) in [
+                        (first, second),
+                        (xterm_colour(first).1, xterm_colour(second).1),
+                    ] {
+                        let [l1, a1, b1] = simulate(first, deficiency);
+                        let [l2, a2, b2] = simulate(second, deficiency);
+
+                        let distance =
+                            ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt();
+
+                        assert!(
+                            distance > 0.025,
+                            "{:?}: {:x} and {:x} are too similar",
+                            palette,
+                            digit,
+                            other
+                        );
+                    }
+                }
+            }
+
+            assert_eq!(palette.digit_colour(16), None);
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use std::convert::From;
+use std::fmt;
+use std::fmt::Display;
+
+use crate::base_line::{FormattableLine, Line};
+
+#[derive(Debug)]
+/// Line with no formatting at all, for use where colours can't be
+/// displayed, but digests still need to be grouped or abbreviated.
+pub struct PlainLine(FormattableLine);
+impl From<String> for PlainLine {
+    fn from(contents: String) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl From<Vec<u8>> for PlainLine {
+    fn from(contents: Vec<u8>) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl Display for PlainLine {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.to_formatted(formatter)
+    }
+}
+
+impl Line for PlainLine {
+    fn get_line(&self) -> &FormattableLine {
+        &self.0
+    }
+
+    /// Leaves the hash or digest as it is.
+    fn format_hash(hash: String) -> String {
+        hash
+    }
+
+    fn write_hash(hash: &str, to: &mut dyn fmt::Write) -> fmt::Result {
+        to.write_str(hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn display_works() {
+        use super::PlainLine;
+
+        assert_eq!(
+            format!(
+                "{}",
+                PlainLine::from(
+                    "MD5 (./src/main.rs) = b7527e0e28c09f6f62dd2d4197d5d225".to_string()
+                )
+            ),
+            "MD5 (./src/main.rs) = b7527e0e28c09f6f62dd2d4197d5d225"
+        )
+    }
+
+    #[test]
+    fn coloursum_with_options_regroups_digests() {
+        use super::PlainLine;
+        use crate::{FormatOptions, Grouping, Line};
+
+        let input: &[u8] = b"MD5 (./src/main.rs) = b7527e0e28c09f6f62dd2d4197d5d225\nb7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs\n";
+        let mut output: Vec<u8> = Vec::new();
+
+        PlainLine::coloursum_with_options(
+            input,
+            &mut output,
+            &FormatOptions {
+                grouping: Some(Grouping::new(8, " ")),
+                ..FormatOptions::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "MD5 (./src/main.rs) = b7527e0e 28c09f6f 62dd2d41 97d5d225\nb7527e0e 28c09f6f 62dd2d41 97d5d225  ./src/main.rs\n"
+        );
+    }
+}
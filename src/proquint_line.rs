@@ -0,0 +1,140 @@
+use std::convert::From;
+use std::fmt;
+use std::fmt::Display;
+
+use crate::base_line::{format_to_string, FormattableLine, Line};
+use crate::checksum_line::decode_digest;
+use crate::format_options::FormatOptions;
+use crate::units::write_units;
+
+const CONSONANTS: &[u8; 16] = b"bdfghjklmnprstvz";
+const VOWELS: &[u8; 4] = b"aiou";
+
+#[derive(Debug)]
+/// Line with proquint ("PRO-nouncable QUINT-uplet") encoding.
+pub struct ProquintLine(FormattableLine);
+impl From<String> for ProquintLine {
+    fn from(contents: String) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl From<Vec<u8>> for ProquintLine {
+    fn from(contents: Vec<u8>) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl Display for ProquintLine {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.to_formatted(formatter)
+    }
+}
+
+impl Line for ProquintLine {
+    fn get_line(&self) -> &FormattableLine {
+        &self.0
+    }
+
+    fn format_hash(hash: String) -> String {
+        format_to_string(&hash, Self::write_hash)
+    }
+
+    /// Formats a base16 or base64-format hash or digest.
+    ///
+    /// Data will be encoded as proquints, each of which spells out two
+    /// bytes with alternating consonants and vowels, separated by dashes.
+    /// Digests with an odd number of bytes can't be encoded, and are
+    /// written unchanged.
+    fn write_hash(hash: &str, to: &mut dyn fmt::Write) -> fmt::Result {
+        Self::write_hash_with_options(hash, to, &FormatOptions::default())
+    }
+
+    /// Formats a base16 or base64-format hash or digest.
+    ///
+    /// Data will be encoded as proquints. If `options.grouping` is set,
+    /// its separator replaces the dash after each group of that many quints.
+    fn write_hash_with_options(
+        hash: &str,
+        to: &mut dyn fmt::Write,
+        options: &FormatOptions,
+    ) -> fmt::Result {
+        match decode_digest(hash) {
+            Some(bytes) if bytes.chunks_exact(2).remainder().is_empty() => {
+                let mut encoded = String::new();
+                write_proquints(&bytes, &mut encoded)?;
+
+                let quints: Vec<&str> = encoded.split('-').collect();
+                write_units(hash, &quints, "-", to, options)
+            }
+            // if there was an error at any point, write the original value
+            _ => to.write_str(hash),
+        }
+    }
+
+    fn writes_units() -> bool {
+        true
+    }
+}
+
+/// Writes `bytes`, which must be of even length, as proquints to `to`.
+fn write_proquints(bytes: &[u8], to: &mut dyn fmt::Write) -> fmt::Result {
+    for (index, pair) in bytes.chunks_exact(2).enumerate() {
+        let word = usize::from(u16::from_be_bytes([pair[0], pair[1]]));
+
+        if index > 0 {
+            to.write_char('-')?;
+        }
+
+        // four bits per consonant, and two per vowel
+        to.write_char(char::from(CONSONANTS[word >> 12]))?;
+        to.write_char(char::from(VOWELS[(word >> 10) & 3]))?;
+        to.write_char(char::from(CONSONANTS[(word >> 6) & 15]))?;
+        to.write_char(char::from(VOWELS[(word >> 4) & 3]))?;
+        to.write_char(char::from(CONSONANTS[word & 15]))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn display_works() {
+        use super::ProquintLine;
+
+        assert_eq!(
+            format!(
+                "{}",
+                ProquintLine::from("b7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs".to_string())
+            ),
+            "ritif-lumav-fogab-nutoz-karit-fujad-nizij-tamoj  ./src/main.rs"
+        )
+    }
+
+    #[test]
+    fn format_hash_works() {
+        use super::ProquintLine;
+        use crate::Line;
+
+        // the examples from the proquint specification
+        assert_eq!(
+            ProquintLine::format_hash("7f000001".to_string()),
+            "lusab-babad"
+        );
+        assert_eq!(
+            ProquintLine::format_hash("3f54dcc1".to_string()),
+            "gutih-tugad"
+        );
+    }
+
+    #[test]
+    fn format_hash_doesnt_crash_on_non_base16_characters() {
+        use super::ProquintLine;
+        use crate::Line;
+
+        assert_eq!(ProquintLine::format_hash("ASD!".to_string()), "ASD!");
+        assert_eq!(ProquintLine::format_hash("b7527e".to_string()), "b7527e");
+        assert_eq!(ProquintLine::format_hash("😄".to_string()), "😄");
+    }
+}
@@ -0,0 +1,261 @@
+use std::convert::From;
+use std::fmt;
+use std::fmt::Display;
+
+use crate::base_line::{FormattableLine, Line};
+use crate::checksum_line::ChecksumLine;
+use crate::format_options::FormatOptions;
+
+/// The width of the field the bishop wanders around.
+const FIELD_WIDTH: usize = 17;
+/// The height of the field the bishop wanders around.
+const FIELD_HEIGHT: usize = 9;
+
+/// The characters drawn for squares visited increasingly often, followed
+/// by those marking where the bishop started and ended up.
+const SYMBOLS: &[u8; 17] = b" .o+=*BOX@%&#/^SE";
+/// The symbol marking where the bishop started.
+const START: u8 = 15;
+/// The symbol marking where the bishop ended up.
+const END: u8 = 16;
+
+#[derive(Debug)]
+/// Line which leaves the digest as it is, but draws it below as an
+/// OpenSSH-style "randomart" picture, like `ssh-keygen -lv` does.
+pub struct RandomartLine(FormattableLine);
+impl From<String> for RandomartLine {
+    fn from(contents: String) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl From<Vec<u8>> for RandomartLine {
+    fn from(contents: Vec<u8>) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl Display for RandomartLine {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.to_formatted(formatter)
+    }
+}
+
+impl Line for RandomartLine {
+    fn get_line(&self) -> &FormattableLine {
+        &self.0
+    }
+
+    /// Leaves the hash or digest as it is; the picture is drawn
+    /// by `write_trailer`.
+    fn format_hash(hash: String) -> String {
+        hash
+    }
+
+    fn write_hash(hash: &str, to: &mut dyn fmt::Write) -> fmt::Result {
+        to.write_str(hash)
+    }
+
+    /// Draws the digest in a 17 by 9 box, using the "drunken bishop"
+    /// algorithm, with the algorithm's name in the top of the frame
+    /// if the line names one.
+    ///
+    /// Nothing is drawn if the digest can't be decoded.
+    fn write_trailer(
+        checksum: &ChecksumLine,
+        to: &mut dyn fmt::Write,
+        _options: &FormatOptions,
+    ) -> fmt::Result {
+        match checksum.digest_bytes() {
+            Some(bytes) => write_randomart(&bytes, checksum.algorithm(), to),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Writes the randomart picture of `bytes` to `to`, with `title`
+/// in the top of the frame.
+fn write_randomart(bytes: &[u8], title: Option<&str>, to: &mut dyn fmt::Write) -> fmt::Result {
+    let mut field = [[0_u8; FIELD_WIDTH]; FIELD_HEIGHT];
+    let (mut x, mut y) = (FIELD_WIDTH / 2, FIELD_HEIGHT / 2);
+
+    for byte in bytes {
+        // each byte makes four moves, two bits at a time,
+        // starting with the least significant bits
+        for step in 0..4 {
+            let bits = byte >> (step * 2);
+
+            x = if bits & 1 == 0 {
+                x.saturating_sub(1)
+            } else {
+                (x + 1).min(FIELD_WIDTH - 1)
+            };
+            y = if bits & 2 == 0 {
+                y.saturating_sub(1)
+            } else {
+                (y + 1).min(FIELD_HEIGHT - 1)
+            };
+
+            // squares stop counting once they reach the last ordinary symbol
+            if field[y][x] < START - 1 {
+                field[y][x] += 1;
+            }
+        }
+    }
+
+    field[FIELD_HEIGHT / 2][FIELD_WIDTH / 2] = START;
+    field[y][x] = END;
+
+    write_border(title, to)?;
+
+    for row in &field {
+        to.write_str("\n|")?;
+        for &visits in row {
+            to.write_char(char::from(SYMBOLS[usize::from(visits)]))?;
+        }
+        to.write_char('|')?;
+    }
+
+    to.write_char('\n')?;
+    write_border(None, to)
+}
+
+/// Writes a horizontal border of the frame, with `title` in the middle,
+/// as long as it fits.
+fn write_border(title: Option<&str>, to: &mut dyn fmt::Write) -> fmt::Result {
+    // the title is written in square brackets
+    let title = title.filter(|title| title.chars().count() + 2 <= FIELD_WIDTH);
+    let title_length = title.map_or(0, |title| title.chars().count() + 2);
+    let before = (FIELD_WIDTH - title_length) / 2;
+
+    to.write_char('+')?;
+    for _ in 0..before {
+        to.write_char('-')?;
+    }
+    if let Some(title) = title {
+        write!(to, "[{}]", title)?;
+    }
+    for _ in before + title_length..FIELD_WIDTH {
+        to.write_char('-')?;
+    }
+    to.write_char('+')
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn display_works() {
+        use super::RandomartLine;
+
+        assert_eq!(
+            format!(
+                "{}",
+                RandomartLine::from(
+                    "MD5 (./src/main.rs) = b7527e0e28c09f6f62dd2d4197d5d225".to_string()
+                )
+            ),
+            [
+                "MD5 (./src/main.rs) = b7527e0e28c09f6f62dd2d4197d5d225",
+                "+------[MD5]------+",
+                "|              Eoo|",
+                "|              o.o|",
+                "|             o . |",
+                "|   .      . o    |",
+                "|    o   S.o.     |",
+                "|     o . =..     |",
+                "|      +.o.+o.    |",
+                "|      ooo.o+.    |",
+                "|     . o.  ..    |",
+                "+-----------------+",
+            ]
+            .join("\n")
+        )
+    }
+
+    #[test]
+    fn coloursum_draws_below_each_line() {
+        use super::RandomartLine;
+        use crate::Line;
+
+        let input: &[u8] = b"00000000000000000000000000000000  ./a\r\nnot a checksum\n";
+        let mut output: Vec<u8> = Vec::new();
+
+        RandomartLine::coloursum(input, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            [
+                "00000000000000000000000000000000  ./a",
+                "+-----------------+",
+                "|E....            |",
+                "|     .           |",
+                "|      .          |",
+                "|       .         |",
+                "|        S        |",
+                "|                 |",
+                "|                 |",
+                "|                 |",
+                "|                 |",
+                "+-----------------+\r\nnot a checksum\n",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn coloursum_with_nul_records_separates_picture_lines_with_newlines() {
+        use super::RandomartLine;
+        use crate::{FormatOptions, Line, RecordSeparator};
+
+        let input: &[u8] = b"00000000000000000000000000000000  ./a\0not a checksum\0";
+        let mut output: Vec<u8> = Vec::new();
+
+        RandomartLine::coloursum_with_options(
+            input,
+            &mut output,
+            &FormatOptions {
+                record_separator: RecordSeparator::Nul,
+                ..FormatOptions::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            [
+                "00000000000000000000000000000000  ./a",
+                "+-----------------+",
+                "|E....            |",
+                "|     .           |",
+                "|      .          |",
+                "|       .         |",
+                "|        S        |",
+                "|                 |",
+                "|                 |",
+                "|                 |",
+                "|                 |",
+                "+-----------------+\0not a checksum\0",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn write_border_works() {
+        use super::write_border;
+
+        let border = |title| {
+            let mut output = String::new();
+            write_border(title, &mut output).unwrap();
+            output
+        };
+
+        assert_eq!(border(None), "+-----------------+");
+        assert_eq!(border(Some("SHA256")), "+----[SHA256]-----+");
+        // titles which don't fit are left out
+        assert_eq!(
+            border(Some("SHAKE256-with-a-long-name")),
+            "+-----------------+"
+        );
+    }
+}
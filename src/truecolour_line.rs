@@ -0,0 +1,157 @@
+use std::convert::From;
+use std::fmt;
+use std::fmt::Display;
+
+use crate::base_line::{format_to_string, FormattableLine, Line};
+use crate::colour::{write_painted, Oklch, TermColour, GOLDEN_ANGLE};
+use crate::format_options::FormatOptions;
+
+#[derive(Debug)]
+/// Line with 24-bit "truecolour" ANSI formatting, using colours
+/// spread evenly through the OKLCh perceptual colour space.
+pub struct TruecolourLine(FormattableLine);
+impl From<String> for TruecolourLine {
+    fn from(contents: String) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl From<Vec<u8>> for TruecolourLine {
+    fn from(contents: Vec<u8>) -> Self {
+        Self(FormattableLine::from(contents))
+    }
+}
+
+impl Display for TruecolourLine {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.to_formatted(formatter)
+    }
+}
+
+impl Line for TruecolourLine {
+    fn get_line(&self) -> &FormattableLine {
+        &self.0
+    }
+
+    fn format_hash(hash: String) -> String {
+        format_to_string(&hash, Self::write_hash)
+    }
+
+    /// Formats a base16-format hash or digest.
+    ///
+    /// Each 8-bit hexadecimal digit will be coloured with a 24-bit colour
+    /// derived from its value, such that adjacent values look distinct.
+    fn write_hash(hash: &str, to: &mut dyn fmt::Write) -> fmt::Result {
+        Self::write_hash_with_options(hash, to, &FormatOptions::default())
+    }
+
+    /// Formats a base16-format hash or digest.
+    ///
+    /// Each 8-bit hexadecimal digit will be coloured with a 24-bit colour
+    /// from the palette `options` selects, downgraded
+    /// to the colours `options.colour_support` allows.
+    fn write_hash_with_options(
+        hash: &str,
+        to: &mut dyn fmt::Write,
+        options: &FormatOptions,
+    ) -> fmt::Result {
+        // if there are any non-base16 characters, write the original value
+        if !hash.bytes().all(|character| character.is_ascii_hexdigit()) {
+            return to.write_str(hash);
+        }
+
+        // map over every two characters
+        for byte in hash.as_bytes().chunks(2) {
+            // these are all ASCII characters, so this can't fail
+            let ord_string = std::str::from_utf8(byte).unwrap_or_default();
+
+            // parse those two characters as a u8, and colour them in
+            match u8::from_str_radix(ord_string, 16) {
+                Ok(ordinal) => {
+                    let (red, green, blue) = options
+                        .palette
+                        .colour(ordinal)
+                        .unwrap_or_else(|| byte_colour(ordinal))
+                        .to_rgb();
+                    write_painted(
+                        to,
+                        TermColour::Rgb(red, green, blue),
+                        ord_string,
+                        options.colour_support,
+                    )?;
+                }
+                Err(_) => to.write_str(ord_string)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Picks the colour for a byte of a digest.
+///
+/// Each step in value turns the hue by the golden angle, and moves to
+/// the next of three lightness levels, so that values which differ by one
+/// (like `4a` and `4b`) are as far apart as possible.
+pub(crate) fn byte_colour(byte: u8) -> Oklch {
+    const LIGHTNESSES: [f32; 3] = [0.66, 0.76, 0.86];
+
+    Oklch {
+        lightness: LIGHTNESSES[usize::from(byte) % LIGHTNESSES.len()],
+        chroma: 0.14,
+        hue: (f32::from(byte) * GOLDEN_ANGLE) % 360.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn display_works() {
+        use super::TruecolourLine;
+
+        assert_eq!(
+            format!("{}", TruecolourLine::from("MD5 (./src/main.rs) = b7527e0e28c09f6f62dd2d4197d5d225".to_string())),
+            "MD5 (./src/main.rs) = \u{1b}[38;2;187;114;194mb7\u{1b}[0m\u{1b}[38;2;173;187;72m52\u{1b}[0m\u{1b}[38;2;214;115;65m7e\u{1b}[0m\u{1b}[38;2;188;225;119m0e\u{1b}[0m\u{1b}[38;2;198;179;54m28\u{1b}[0m\u{1b}[38;2;133;159;45mc0\u{1b}[0m\u{1b}[38;2;101;143;231m9f\u{1b}[0m\u{1b}[38;2;88;168;86m6f\u{1b}[0m\u{1b}[38;2;124;236;170m62\u{1b}[0m\u{1b}[38;2;139;235;158mdd\u{1b}[0m\u{1b}[38;2;201;127;20m2d\u{1b}[0m\u{1b}[38;2;215;198;255m41\u{1b}[0m\u{1b}[38;2;93;186;255m97\u{1b}[0m\u{1b}[38;2;119;162;60md5\u{1b}[0m\u{1b}[38;2;193;133;0md2\u{1b}[0m\u{1b}[38;2;248;147;94m25\u{1b}[0m"
+        )
+    }
+
+    #[test]
+    fn format_hash_works() {
+        use super::TruecolourLine;
+        use crate::Line;
+
+        assert_eq!(
+            TruecolourLine::format_hash("00014a4bff".to_string()),
+            "\u{1b}[38;2;213;106;143m00\u{1b}[0m\u{1b}[38;2;132;198;107m01\u{1b}[0m\u{1b}[38;2;237;208;90m4a\u{1b}[0m\u{1b}[38;2;0;159;216m4b\u{1b}[0m\u{1b}[38;2;85;168;88mff\u{1b}[0m"
+        );
+    }
+
+    #[test]
+    fn format_hash_doesnt_crash_on_non_base16_characters() {
+        use super::TruecolourLine;
+        use crate::Line;
+
+        assert_eq!(TruecolourLine::format_hash("ASDF".to_string()), "ASDF");
+        assert_eq!(TruecolourLine::format_hash("😄".to_string()), "😄");
+    }
+
+    #[test]
+    fn adjacent_bytes_have_distinct_colours() {
+        use super::byte_colour;
+
+        for byte in 0..u8::MAX {
+            let (l1, a1, b1) = byte_colour(byte).to_oklab();
+            let (l2, a2, b2) = byte_colour(byte + 1).to_oklab();
+
+            // Euclidean distance in OKLab; around 0.02 is just noticeable
+            let distance = ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt();
+
+            assert!(
+                distance > 0.15,
+                "{:02x} and {:02x} are too similar",
+                byte,
+                byte + 1
+            );
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use std::fmt;
+
+use crate::abbreviation::write_ellipsis;
+use crate::format_options::FormatOptions;
+
+/// Writes `units`, the words or syllables a formatter spells `digest`
+/// out in, to `to`, with `separator` between each of them.
+///
+/// If `options.abbreviation` is set, it counts units rather than
+/// characters, so only whole units are left out, like `Bravo Seven … Two Five`.
+///
+/// If `options.grouping` is set, its separator is written between each
+/// group of that many units, so that no unit is split across groups.
+/// Spaces between units are kept either side of a visible group separator,
+/// so groups stand out, like `Bravo Seven / Five Two`; any other unit
+/// separator, or a whitespace group separator, is written on its own,
+/// like `lusab/babad` or `Bravo Seven Five Two`.
+pub(crate) fn write_units<S: AsRef<str>>(
+    digest: &str,
+    units: &[S],
+    separator: &str,
+    to: &mut dyn fmt::Write,
+    options: &FormatOptions,
+) -> fmt::Result {
+    let abbreviation = match options.abbreviation {
+        // only abbreviate if it makes the digest shorter
+        Some(abbreviation) if units.len() > abbreviation.shown_length() => abbreviation,
+        _ => return write_groups(units, separator, to, options),
+    };
+
+    // the head and tail are grouped separately, so each starts a new group
+    let (head, tail) = (
+        &units[..abbreviation.head],
+        &units[units.len() - abbreviation.tail..],
+    );
+
+    write_groups(head, separator, to, options)?;
+    if !head.is_empty() {
+        to.write_str(separator)?;
+    }
+    write_ellipsis(digest, to, options)?;
+    if !tail.is_empty() {
+        to.write_str(separator)?;
+    }
+    write_groups(tail, separator, to, options)
+}
+
+/// Writes `units` to `to`, with `separator` between each of them,
+/// split into groups as configured by `options.grouping`.
+fn write_groups<S: AsRef<str>>(
+    units: &[S],
+    separator: &str,
+    to: &mut dyn fmt::Write,
+    options: &FormatOptions,
+) -> fmt::Result {
+    let grouping = options
+        .grouping
+        .as_ref()
+        .filter(|grouping| grouping.size > 0);
+    let group_size = grouping.map_or(units.len(), |grouping| grouping.size);
+
+    for (index, group) in units.chunks(group_size.max(1)).enumerate() {
+        if index > 0 {
+            if let Some(grouping) = grouping {
+                if separator == " " && !grouping.separator.trim().is_empty() {
+                    write!(to, " {} ", grouping.separator)?;
+                } else {
+                    to.write_str(&grouping.separator)?;
+                }
+            }
+        }
+
+        for (index, unit) in group.iter().enumerate() {
+            if index > 0 {
+                to.write_str(separator)?;
+            }
+            to.write_str(unit.as_ref())?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn write_units_works() {
+        use super::write_units;
+        use crate::{FormatOptions, Grouping};
+
+        let write = |units: &[&str], separator, grouping| {
+            let mut output = String::new();
+            write_units(
+                "b7527",
+                units,
+                separator,
+                &mut output,
+                &FormatOptions {
+                    grouping,
+                    ..FormatOptions::default()
+                },
+            )
+            .unwrap();
+            output
+        };
+        let words = ["Bravo", "Seven", "Five", "Two", "Seven"];
+
+        assert_eq!(write(&words, " ", None), "Bravo Seven Five Two Seven");
+        assert_eq!(
+            write(&words, " ", Some(Grouping::new(2, "/"))),
+            "Bravo Seven / Five Two / Seven"
+        );
+        assert_eq!(
+            write(&words, "-", Some(Grouping::new(2, " "))),
+            "Bravo-Seven Five-Two Seven"
+        );
+        assert_eq!(
+            write(&words, " ", Some(Grouping::new(2, " "))),
+            "Bravo Seven Five Two Seven"
+        );
+        assert_eq!(
+            write(&words, " ", Some(Grouping::new(2, "\t"))),
+            "Bravo Seven\tFive Two\tSeven"
+        );
+        assert_eq!(
+            write(&words, " ", Some(Grouping::new(0, "/"))),
+            "Bravo Seven Five Two Seven"
+        );
+        assert_eq!(write(&[], " ", Some(Grouping::new(2, "/"))), "");
+    }
+
+    #[test]
+    fn write_units_abbreviates_whole_units() {
+        use super::write_units;
+        use crate::{Abbreviation, ColourSupport, FormatOptions, Grouping};
+
+        let write = |units: &[&str], separator, abbreviation, grouping| {
+            let mut output = String::new();
+            write_units(
+                "b7527e0",
+                units,
+                separator,
+                &mut output,
+                &FormatOptions {
+                    abbreviation: Some(abbreviation),
+                    grouping,
+                    colour_support: ColourSupport::None,
+                    ..FormatOptions::default()
+                },
+            )
+            .unwrap();
+            output
+        };
+        let words = ["Bravo", "Seven", "Five", "Two", "Seven", "Echo", "Zero"];
+
+        assert_eq!(
+            write(&words, " ", Abbreviation::new(2, 2), None),
+            "Bravo Seven … Echo Zero"
+        );
+        assert_eq!(write(&words, "-", Abbreviation::new(0, 1), None), "…-Zero");
+        assert_eq!(
+            write(
+                &words,
+                " ",
+                Abbreviation::new(3, 2),
+                Some(Grouping::new(2, "/"))
+            ),
+            "Bravo Seven / Five … Echo Zero"
+        );
+        // nothing is left out of short digests
+        assert_eq!(
+            write(&words, " ", Abbreviation::new(3, 3), None),
+            "Bravo Seven Five Two Seven Echo Zero"
+        );
+        assert_eq!(
+            write(&words, " ", Abbreviation::new(usize::MAX, 1), None),
+            "Bravo Seven Five Two Seven Echo Zero"
+        );
+    }
+
+    #[test]
+    fn coloursum_groups_and_abbreviates_formatters_by_unit() {
+        use crate::{coloursum, Abbreviation, ColourSupport, FormatOptions, FormatterRegistry};
+        use crate::{Grouping, Line, PGPWordLine};
+
+        let input: &[u8] = b"b7527e0e28c09f6f62dd2d4197d5d225  ./src/main.rs\n";
+        let options = FormatOptions {
+            abbreviation: Some(Abbreviation::new(3, 2)),
+            grouping: Some(Grouping::new(2, "/")),
+            colour_support: ColourSupport::None,
+            ..FormatOptions::default()
+        };
+
+        let mut output: Vec<u8> = Vec::new();
+        PGPWordLine::coloursum_with_options(input, &mut output, &options).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "seabird enrollment / locale … standard caravan  ./src/main.rs\n"
+        );
+
+        let registry = FormatterRegistry::default();
+        let mut output: Vec<u8> = Vec::new();
+        coloursum(
+            registry.get("proquint").unwrap(),
+            input,
+            &mut output,
+            &options,
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "ritif-lumav/fogab-…-nizij-tamoj  ./src/main.rs\n"
+        );
+    }
+}